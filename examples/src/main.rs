@@ -1,6 +1,5 @@
 use clap::Parser;
 use qdrant::*;
-use serde_json::json;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -23,97 +22,64 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
 
     let collection_name = "my_test";
 
-    // check if the collection exists. If present, delete it.
-    if let Ok(true) = client.collection_exists(collection_name).await {
-        println!("Collection `{}` exists", collection_name);
-        match client.delete_collection(collection_name).await {
-            Ok(_) => println!("Collection `{}` deleted", collection_name),
-            Err(e) => println!("Error deleting collection: {:?}", e),
-        }
-    };
-
-    // Create a collection with 10-dimensional vectors
-    let r = client.create_collection("my_test", 4).await;
-    println!("Create collection result is {:?}", r);
-
+    // Create a collection with 4-dimensional vectors, deleting any previous one
+    let r = client
+        .recreate_collection(collection_name, CreateCollectionOptions::new(4))
+        .await;
+    println!("Recreate collection result is {:?}", r);
+
+    let cities: [(u64, [f32; 4], &str); 6] = [
+        (1, [0.05, 0.61, 0.76, 0.74], "Berlin"),
+        (2, [0.19, 0.81, 0.75, 0.11], "London"),
+        (3, [0.36, 0.55, 0.47, 0.94], "Moscow"),
+        (4, [0.18, 0.01, 0.85, 0.80], "New York"),
+        (5, [0.24, 0.18, 0.22, 0.44], "Beijing"),
+        (6, [0.35, 0.08, 0.11, 0.44], "Mumbai"),
+    ];
     let mut points = Vec::<Point>::new();
-    points.push(Point {
-        id: PointId::Num(1),
-        vector: vec![0.05, 0.61, 0.76, 0.74],
-        payload: json!({"city": "Berlin"}).as_object().map(|m| m.to_owned()),
-    });
-    points.push(Point {
-        id: PointId::Num(2),
-        vector: vec![0.19, 0.81, 0.75, 0.11],
-        payload: json!({"city": "London"}).as_object().map(|m| m.to_owned()),
-    });
-    points.push(Point {
-        id: PointId::Num(3),
-        vector: vec![0.36, 0.55, 0.47, 0.94],
-        payload: json!({"city": "Moscow"}).as_object().map(|m| m.to_owned()),
-    });
-    points.push(Point {
-        id: PointId::Num(4),
-        vector: vec![0.18, 0.01, 0.85, 0.80],
-        payload: json!({"city": "New York"})
-            .as_object()
-            .map(|m| m.to_owned()),
-    });
-    points.push(Point {
-        id: PointId::Num(5),
-        vector: vec![0.24, 0.18, 0.22, 0.44],
-        payload: json!({"city": "Beijing"}).as_object().map(|m| m.to_owned()),
-    });
-    points.push(Point {
-        id: PointId::Num(6),
-        vector: vec![0.35, 0.08, 0.11, 0.44],
-        payload: json!({"city": "Mumbai"}).as_object().map(|m| m.to_owned()),
-    });
-
-    let r = client.upsert_points("my_test", points).await;
+    for (id, vector, city) in cities {
+        points.push(
+            Point::builder()
+                .id(id)
+                .vector(vector.to_vec())
+                .payload_field("city", city)
+                .build()?,
+        );
+    }
+
+    let r = client.upsert_points("my_test", points.clone()).await;
     println!("Upsert points result is {:?}", r);
 
+    let size = client.collection_info("my_test").await?;
+    println!("The collection size is {}", size);
+
+    // Upserts are idempotent, so replaying one, e.g. after a timeout, does not duplicate points
+    client.upsert_points("my_test", points).await?;
     println!(
-        "The collection size is {}",
-        client.collection_info("my_test").await
+        "The collection size is {} after replaying the upsert",
+        client.collection_info("my_test").await?
     );
 
-    let p = client.get_point("my_test", &PointId::from(2)).await;
+    let p: Point = client.get_point("my_test", 2).await?;
     println!("The second point is {:?}", p);
 
-    let ps = client
-        .get_points(
-            "my_test",
-            &vec![1, 2, 3, 4, 5, 6]
-                .into_iter()
-                .map(|id| PointId::from(id))
-                .collect::<Vec<_>>(),
-        )
-        .await;
+    let ps: Vec<Point> = client.get_points("my_test", vec![1, 2, 3, 4, 5, 6]).await?;
     println!("The 1-6 points are {:?}", ps);
 
     let q = vec![0.2, 0.1, 0.9, 0.7];
-    let r = client.search_points("my_test", q, 2, None).await;
+    let r: Result<Vec<ScoredPoint>, _> = client.search_points("my_test", q, 2, None).await;
     println!("Search result points are {:?}", r);
 
-    let r = client
-        .delete_points(
-            "my_test",
-            &vec![1, 4]
-                .into_iter()
-                .map(|id| PointId::from(id))
-                .collect::<Vec<_>>(),
-        )
-        .await;
+    let r = client.delete_points("my_test", vec![1, 4]).await;
     println!("Delete points result is {:?}", r);
 
     println!(
         "The collection size is {}",
-        client.collection_info("my_test").await
+        client.collection_info("my_test").await?
     );
 
     let q = vec![0.2, 0.1, 0.9, 0.7];
-    let r = client.search_points("my_test", q, 2, None).await;
+    let r: Result<Vec<ScoredPoint>, _> = client.search_points("my_test", q, 2, None).await;
     println!("Search result points are {:?}", r);
     Ok(())
 }
@@ -0,0 +1,162 @@
+//! A blocking client for callers that are not in an async context, e.g. CLI tools and sync test harnesses.
+//!
+//! Unlike `reqwest::blocking`, which runs the requests on a background thread,
+//! the client drives the async client on its own current-thread tokio runtime,
+//! so it also works on WasmEdge, where threads are not available.
+//! Its methods must not be called from within an async runtime.
+
+use crate::{
+    CreateCollectionOptions, Point, PointId, QdrantBuilder, QdrantError, ScoredPoint, SearchOptions,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::runtime::Runtime;
+
+/// The blocking counterpart of `qdrant::Qdrant`
+pub struct Qdrant {
+    inner: crate::Qdrant,
+    runtime: Runtime,
+}
+
+impl Qdrant {
+    /// Creates a client for the Qdrant service at the given url.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying HTTP client or the runtime cannot be initialized.
+    pub fn new_with_url(url_base_: String) -> Qdrant {
+        Qdrant::from_builder(QdrantBuilder::new().url(url_base_))
+            .expect("[qdrant] Failed to build the blocking client")
+    }
+
+    pub fn new() -> Qdrant {
+        Qdrant::new_with_url("http://localhost:6333".to_string())
+    }
+
+    /// Creates a client configured by the builder
+    pub fn from_builder(builder: QdrantBuilder) -> Result<Qdrant, QdrantError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| QdrantError::Unexpected(format!("Failed to start the runtime: {}", e)))?;
+        Ok(Qdrant {
+            inner: builder.build()?,
+            runtime,
+        })
+    }
+
+    pub fn set_api_key(&mut self, api_key: impl Into<String>) {
+        self.inner.set_api_key(api_key);
+    }
+
+    pub fn collection_info(&self, collection_name: &str) -> Result<u64, QdrantError> {
+        self.runtime
+            .block_on(self.inner.collection_info(collection_name))
+    }
+
+    pub fn create_collection(&self, collection_name: &str, size: u32) -> Result<(), QdrantError> {
+        self.runtime
+            .block_on(self.inner.create_collection(collection_name, size))
+    }
+
+    pub fn create_collection_with_options(
+        &self,
+        collection_name: &str,
+        options: CreateCollectionOptions,
+    ) -> Result<(), QdrantError> {
+        self.runtime.block_on(
+            self.inner
+                .create_collection_with_options(collection_name, options),
+        )
+    }
+
+    pub fn list_collections(&self) -> Result<Vec<String>, QdrantError> {
+        self.runtime.block_on(self.inner.list_collections())
+    }
+
+    pub fn collection_exists(&self, collection_name: &str) -> Result<bool, QdrantError> {
+        self.runtime
+            .block_on(self.inner.collection_exists(collection_name))
+    }
+
+    pub fn delete_collection(&self, collection_name: &str) -> Result<(), QdrantError> {
+        self.runtime
+            .block_on(self.inner.delete_collection(collection_name))
+    }
+
+    pub fn delete_collection_if_exists(&self, collection_name: &str) -> Result<(), QdrantError> {
+        self.runtime
+            .block_on(self.inner.delete_collection_if_exists(collection_name))
+    }
+
+    pub fn upsert_points<P: Serialize>(
+        &self,
+        collection_name: &str,
+        points: Vec<Point<P>>,
+    ) -> Result<(), QdrantError> {
+        self.runtime
+            .block_on(self.inner.upsert_points(collection_name, points))
+    }
+
+    pub fn search_points<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        vector: Vec<f32>,
+        limit: u64,
+        score_threshold: Option<f32>,
+    ) -> Result<Vec<ScoredPoint<P>>, QdrantError> {
+        self.runtime.block_on(self.inner.search_points(
+            collection_name,
+            vector,
+            limit,
+            score_threshold,
+        ))
+    }
+
+    pub fn search_points_with_options<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        vector: Vec<f32>,
+        limit: u64,
+        options: &SearchOptions,
+    ) -> Result<Vec<ScoredPoint<P>>, QdrantError> {
+        self.runtime.block_on(self.inner.search_points_with_options(
+            collection_name,
+            vector,
+            limit,
+            options,
+        ))
+    }
+
+    pub fn get_points<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        ids: impl IntoIterator<Item = impl Into<PointId>>,
+    ) -> Result<Vec<Point<P>>, QdrantError> {
+        self.runtime
+            .block_on(self.inner.get_points(collection_name, ids))
+    }
+
+    pub fn get_point<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        id: impl Into<PointId>,
+    ) -> Result<Point<P>, QdrantError> {
+        self.runtime
+            .block_on(self.inner.get_point(collection_name, id))
+    }
+
+    pub fn delete_points(
+        &self,
+        collection_name: &str,
+        ids: impl IntoIterator<Item = impl Into<PointId>>,
+    ) -> Result<(), QdrantError> {
+        self.runtime
+            .block_on(self.inner.delete_points(collection_name, ids))
+    }
+}
+
+impl Default for Qdrant {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,318 @@
+//! A builder for the filters of searches, scrolls, counts and deletes.
+//!
+//! A `Filter` converts into the JSON `Value` the `filter` options take, e.g.
+//! `Filter::default().must(Condition::match_text("body", "rust")).into()`.
+
+use crate::PointId;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// A filter combining conditions. A point matches if it matches all the `must` conditions,
+/// at least one of the `should` conditions, and none of the `must_not` conditions.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Filter {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub must: Vec<Condition>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub should: Vec<Condition>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub must_not: Vec<Condition>,
+}
+
+impl Filter {
+    /// Adds a condition all matching points must match
+    pub fn must(mut self, condition: Condition) -> Self {
+        self.must.push(condition);
+        self
+    }
+
+    /// Adds a condition of which matching points must match at least one
+    pub fn should(mut self, condition: Condition) -> Self {
+        self.should.push(condition);
+        self
+    }
+
+    /// Adds a condition no matching point may match
+    pub fn must_not(mut self, condition: Condition) -> Self {
+        self.must_not.push(condition);
+        self
+    }
+}
+
+impl From<Filter> for Value {
+    fn from(filter: Filter) -> Self {
+        json!(filter)
+    }
+}
+
+/// A condition of a filter
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Condition {
+    /// A condition on the value of a payload field
+    Field(FieldCondition),
+    /// The field is missing, `null` or an empty array, `{"is_empty": {"key": ...}}`
+    IsEmpty { is_empty: PayloadField },
+    /// The field is `null`, `{"is_null": {"key": ...}}`
+    IsNull { is_null: PayloadField },
+    /// An element of an array of objects matches the filter, `{"nested": {"key": ..., "filter": ...}}`
+    Nested { nested: NestedCondition },
+    /// The point has one of the ids, `{"has_id": [...]}`
+    HasId { has_id: Vec<PointId> },
+}
+
+impl Condition {
+    /// The field has exactly the value, a keyword, an integer or a bool
+    pub fn matches(key: impl Into<String>, value: impl Into<Value>) -> Condition {
+        Condition::field(
+            key,
+            FieldCondition {
+                r#match: Some(Match::Value {
+                    value: value.into(),
+                }),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// The field contains the text. Requires a full-text payload index on the field;
+    /// without one, the field must contain the text as an exact substring.
+    pub fn match_text(key: impl Into<String>, text: impl Into<String>) -> Condition {
+        Condition::field(
+            key,
+            FieldCondition {
+                r#match: Some(Match::Text { text: text.into() }),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// The field has any of the values
+    pub fn match_any(key: impl Into<String>, values: Vec<Value>) -> Condition {
+        Condition::field(
+            key,
+            FieldCondition {
+                r#match: Some(Match::Any { any: values }),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// The field has none of the values
+    pub fn match_except(key: impl Into<String>, values: Vec<Value>) -> Condition {
+        Condition::field(
+            key,
+            FieldCondition {
+                r#match: Some(Match::Except { except: values }),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// The numeric field is within the range
+    pub fn range(key: impl Into<String>, range: Range) -> Condition {
+        Condition::field(
+            key,
+            FieldCondition {
+                range: Some(range),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// The geo point field is within `radius` meters of `center`
+    pub fn geo_radius(key: impl Into<String>, center: GeoPoint, radius: f64) -> Condition {
+        Condition::field(
+            key,
+            FieldCondition {
+                geo_radius: Some(GeoRadius { center, radius }),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// The geo point field is within the rectangle
+    pub fn geo_bounding_box(
+        key: impl Into<String>,
+        top_left: GeoPoint,
+        bottom_right: GeoPoint,
+    ) -> Condition {
+        Condition::field(
+            key,
+            FieldCondition {
+                geo_bounding_box: Some(GeoBoundingBox {
+                    top_left,
+                    bottom_right,
+                }),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// The geo point field is within the polygon
+    pub fn geo_polygon(key: impl Into<String>, polygon: GeoPolygon) -> Condition {
+        Condition::field(
+            key,
+            FieldCondition {
+                geo_polygon: Some(polygon),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// The number of values of the field, e.g. the length of an array, is within the range
+    pub fn values_count(key: impl Into<String>, count: ValuesCount) -> Condition {
+        Condition::field(
+            key,
+            FieldCondition {
+                values_count: Some(count),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// The field is missing, `null` or an empty array
+    pub fn is_empty(key: impl Into<String>) -> Condition {
+        Condition::IsEmpty {
+            is_empty: PayloadField { key: key.into() },
+        }
+    }
+
+    /// The field is `null`. Unlike `is_empty`, missing fields do not match.
+    pub fn is_null(key: impl Into<String>) -> Condition {
+        Condition::IsNull {
+            is_null: PayloadField { key: key.into() },
+        }
+    }
+
+    /// A single element of the array of objects `key` matches all of the filter.
+    /// The keys of the conditions of the filter are relative to the elements, e.g. `rating` for `reviews[].rating`.
+    pub fn nested(key: impl Into<String>, filter: Filter) -> Condition {
+        Condition::Nested {
+            nested: NestedCondition {
+                key: key.into(),
+                filter,
+            },
+        }
+    }
+
+    /// The point has one of the ids, e.g. to restrict a search to candidate points
+    pub fn has_id(ids: &[PointId]) -> Condition {
+        Condition::HasId {
+            has_id: ids.to_vec(),
+        }
+    }
+
+    fn field(key: impl Into<String>, condition: FieldCondition) -> Condition {
+        Condition::Field(FieldCondition {
+            key: key.into(),
+            ..condition
+        })
+    }
+}
+
+/// A condition on the value of the payload field `key`. Only one of the other fields is expected to be set.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FieldCondition {
+    pub key: String,
+    #[serde(rename = "match", skip_serializing_if = "Option::is_none")]
+    pub r#match: Option<Match>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<Range>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo_radius: Option<GeoRadius>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo_bounding_box: Option<GeoBoundingBox>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo_polygon: Option<GeoPolygon>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values_count: Option<ValuesCount>,
+}
+
+/// A filter applied to each element of the array of objects `key`
+#[derive(Debug, Clone, Serialize)]
+pub struct NestedCondition {
+    pub key: String,
+    pub filter: Filter,
+}
+
+/// A payload field referred to by a condition
+#[derive(Debug, Clone, Serialize)]
+pub struct PayloadField {
+    pub key: String,
+}
+
+/// How the value of a field is matched
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Match {
+    /// The exact value, `{"value": ...}`
+    Value { value: Value },
+    /// A full-text match, `{"text": ...}`
+    Text { text: String },
+    /// Any of the values, `{"any": [...]}`
+    Any { any: Vec<Value> },
+    /// None of the values, `{"except": [...]}`
+    Except { except: Vec<Value> },
+}
+
+/// The bounds of a range condition. Unset bounds are open.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Range {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gt: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gte: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lt: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lte: Option<f64>,
+}
+
+/// The bounds of a values count condition. Unset bounds are open.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValuesCount {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gt: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gte: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lt: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lte: Option<u64>,
+}
+
+/// A location on Earth, in degrees
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct GeoPoint {
+    pub lon: f64,
+    pub lat: f64,
+}
+
+/// A circle on Earth, with its radius in meters
+#[derive(Debug, Clone, Serialize)]
+pub struct GeoRadius {
+    pub center: GeoPoint,
+    pub radius: f64,
+}
+
+/// A rectangle on Earth, given by its corners
+#[derive(Debug, Clone, Serialize)]
+pub struct GeoBoundingBox {
+    pub top_left: GeoPoint,
+    pub bottom_right: GeoPoint,
+}
+
+/// A closed line on Earth. The first and the last points must be the same.
+#[derive(Debug, Clone, Serialize)]
+pub struct GeoLineString {
+    pub points: Vec<GeoPoint>,
+}
+
+/// A polygon on Earth, given by its outer boundary and the boundaries of its holes
+#[derive(Debug, Clone, Serialize)]
+pub struct GeoPolygon {
+    pub exterior: GeoLineString,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub interiors: Vec<GeoLineString>,
+}
@@ -2,18 +2,177 @@
 #[macro_use]
 extern crate log;
 
-use anyhow::{anyhow, bail, Error};
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod filter;
+
+use flate2::{write::GzEncoder, Compression};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use reqwest::header::{HeaderMap, HeaderValue};
+pub use reqwest::Certificate;
+use reqwest::Method;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::json;
 use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// The error type of the client
+#[derive(Debug)]
+pub enum QdrantError {
+    /// The collection does not exist
+    CollectionNotFound(String),
+
+    /// The collection already exists
+    CollectionExists(String),
+
+    /// Qdrant responded with a non-success status code. `action` is what the client tried to do,
+    /// e.g. `search points`, and `body` holds the reason reported by Qdrant.
+    Http {
+        status: u16,
+        body: String,
+        action: String,
+    },
+
+    /// The request could not be sent, or the response could not be received
+    Transport(reqwest::Error),
+
+    /// A JSON body could not be serialized or deserialized
+    Decode(serde_json::Error),
+
+    /// An argument was rejected before sending the request
+    InvalidInput(String),
+
+    /// The response does not have the expected shape
+    Unexpected(String),
+}
+
+impl Display for QdrantError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QdrantError::CollectionNotFound(name) => {
+                write!(f, "[qdrant] Not found collection '{}'", name)
+            }
+            QdrantError::CollectionExists(name) => {
+                write!(f, "[qdrant] Collection '{}' already exists", name)
+            }
+            QdrantError::Http {
+                status,
+                body,
+                action,
+            } => {
+                write!(
+                    f,
+                    "[qdrant] Failed to {}, status {}: {}",
+                    action, status, body
+                )
+            }
+            QdrantError::Transport(e) => write!(f, "[qdrant] {}", e),
+            QdrantError::Decode(e) => write!(f, "[qdrant] Invalid JSON: {}", e),
+            QdrantError::InvalidInput(msg) => write!(f, "[qdrant] {}", msg),
+            QdrantError::Unexpected(msg) => write!(f, "[qdrant] {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for QdrantError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            QdrantError::Transport(e) => Some(e),
+            QdrantError::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for QdrantError {
+    fn from(e: reqwest::Error) -> Self {
+        QdrantError::Transport(e)
+    }
+}
+
+impl From<serde_json::Error> for QdrantError {
+    fn from(e: serde_json::Error) -> Self {
+        QdrantError::Decode(e)
+    }
+}
+
+/// The id of a point, an unsigned integer or a UUID.
+///
+/// When deserialized, strings holding an unsigned integer, e.g. `"42"`, become `Num` ids.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 #[serde(untagged)]
 pub enum PointId {
     Uuid(String),
     Num(u64),
 }
+impl PointId {
+    /// Creates a UUID point id, checking that the string is a valid UUID
+    pub fn uuid(uuid: &str) -> Result<PointId, QdrantError> {
+        match uuid::Uuid::parse_str(uuid) {
+            Ok(_) => Ok(PointId::Uuid(uuid.to_string())),
+            Err(e) => Err(QdrantError::InvalidInput(format!(
+                "Invalid UUID point id '{}': {}",
+                uuid, e
+            ))),
+        }
+    }
+
+    /// Parses a point id from a string: an unsigned integer like `"42"` gives a `Num` id, anything else a `Uuid` id
+    pub fn parse(id: &str) -> PointId {
+        match id.parse::<u64>() {
+            Ok(num) => PointId::Num(num),
+            Err(_) => PointId::Uuid(id.to_string()),
+        }
+    }
+
+    /// Derives a UUID point id from the content, so the same content always gets the same id
+    /// and re-ingesting it overwrites the point instead of duplicating it.
+    ///
+    /// The id is a UUID v5 of the content, in a namespace derived from `namespace`,
+    /// so identical content in different namespaces (e.g. collections) gets different ids.
+    pub fn from_content(namespace: &str, content: impl AsRef<[u8]>) -> PointId {
+        let namespace = uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_OID, namespace.as_bytes());
+        PointId::Uuid(uuid::Uuid::new_v5(&namespace, content.as_ref()).to_string())
+    }
+
+    /// Checks that a UUID point id holds a valid UUID. Numeric ids are always valid.
+    pub fn validate(&self) -> Result<(), QdrantError> {
+        match self {
+            PointId::Uuid(uuid) => PointId::uuid(uuid).map(|_| ()),
+            PointId::Num(_) => Ok(()),
+        }
+    }
+
+    /// Returns the id in the form Qdrant reports it, i.e. UUIDs lowercase and hyphenated
+    fn normalized(&self) -> PointId {
+        match self {
+            PointId::Uuid(uuid) => match uuid::Uuid::parse_str(uuid) {
+                Ok(uuid) => PointId::Uuid(uuid.hyphenated().to_string()),
+                Err(_) => self.clone(),
+            },
+            PointId::Num(_) => self.clone(),
+        }
+    }
+}
+impl<'de> Deserialize<'de> for PointId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawPointId {
+            Num(u64),
+            Str(String),
+        }
+
+        Ok(match RawPointId::deserialize(deserializer)? {
+            RawPointId::Num(num) => PointId::Num(num),
+            RawPointId::Str(id) => PointId::parse(&id),
+        })
+    }
+}
 impl From<u64> for PointId {
     fn from(num: u64) -> Self {
         PointId::Num(num)
@@ -24,6 +183,16 @@ impl From<String> for PointId {
         PointId::Uuid(uuid)
     }
 }
+impl From<&str> for PointId {
+    fn from(uuid: &str) -> Self {
+        PointId::Uuid(uuid.to_string())
+    }
+}
+impl From<&PointId> for PointId {
+    fn from(id: &PointId) -> Self {
+        id.clone()
+    }
+}
 impl Display for PointId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -33,730 +202,4500 @@ impl Display for PointId {
     }
 }
 
+/// A sparse vector, holding only the non-zero values and their indices
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SparseVector {
+    pub indices: Vec<u32>,
+    pub values: Vec<f32>,
+}
+
+/// A named vector of a point, either dense or sparse
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Vector {
+    Dense(Vec<f32>),
+    Sparse(SparseVector),
+}
+impl From<Vec<f32>> for Vector {
+    fn from(vector: Vec<f32>) -> Self {
+        Vector::Dense(vector)
+    }
+}
+impl From<SparseVector> for Vector {
+    fn from(vector: SparseVector) -> Self {
+        Vector::Sparse(vector)
+    }
+}
+
+/// The vectors of a point: a single unnamed dense vector, or a set of named dense and sparse vectors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum VectorData {
+    Dense(Vec<f32>),
+    Named(HashMap<String, Vector>),
+}
+impl From<Vec<f32>> for VectorData {
+    fn from(vector: Vec<f32>) -> Self {
+        VectorData::Dense(vector)
+    }
+}
+impl From<HashMap<String, Vector>> for VectorData {
+    fn from(vectors: HashMap<String, Vector>) -> Self {
+        VectorData::Named(vectors)
+    }
+}
+
 /// The point struct.
 /// A point is a record consisting of a vector and an optional payload.
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// The payload type defaults to a JSON object, but any type implementing
+/// `Serialize` and `DeserializeOwned` can be used for strongly-typed payloads.
+/// Integer payload values, up to `u64::MAX` and down to `i64::MIN`, are sent and read back exactly;
+/// only numbers with a fractional part or an exponent are converted to `f64`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Point {
+pub struct Point<P = Map<String, Value>> {
     /// Id of the point
     pub id: PointId,
 
-    /// Vectors
-    pub vector: Vec<f32>,
+    /// Vectors. `None` for payload-only points, or when the point was retrieved without its vector.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector: Option<VectorData>,
 
-    /// Additional information along with vectors
-    pub payload: Option<Map<String, Value>>,
+    /// Additional information along with vectors. Left out of the request if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<P>,
+}
+
+impl Point {
+    /// Starts building a point with a JSON object payload
+    pub fn builder() -> PointBuilder {
+        PointBuilder::default()
+    }
+}
+
+/// Builder for a `Point` with a JSON object payload
+#[derive(Debug, Default)]
+pub struct PointBuilder {
+    id: Option<PointId>,
+    vector: Option<VectorData>,
+    payload: Option<Map<String, Value>>,
+}
+
+impl PointBuilder {
+    pub fn id(mut self, id: impl Into<PointId>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    pub fn vector(mut self, vector: impl Into<VectorData>) -> Self {
+        self.vector = Some(vector.into());
+        self
+    }
+
+    /// Sets a field of the payload, keeping the other fields
+    pub fn payload_field(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.payload
+            .get_or_insert_with(Map::new)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Replaces the whole payload
+    pub fn payload(mut self, payload: Map<String, Value>) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+
+    /// Builds the point. Fails if no id was set.
+    pub fn build(self) -> Result<Point, QdrantError> {
+        match self.id {
+            Some(id) => Ok(Point {
+                id,
+                vector: self.vector,
+                payload: self.payload,
+            }),
+            None => Err(QdrantError::InvalidInput("The point has no id".to_string())),
+        }
+    }
 }
 
 /// The point struct with the score returned by searching
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ScoredPoint {
+pub struct ScoredPoint<P = Map<String, Value>> {
     /// Id of the point
     pub id: PointId,
 
     /// Vectors
-    pub vector: Option<Vec<f32>>,
+    pub vector: Option<VectorData>,
 
     /// Additional information along with vectors
-    pub payload: Option<Map<String, Value>>,
+    pub payload: Option<P>,
 
     /// Points vector distance to the query vector
     pub score: f32,
 }
+impl<P> ScoredPoint<P> {
+    /// Whether this point is strictly closer to the query than `other`,
+    /// given the distance of the collection, as lower scores are better for Euclid and Manhattan
+    pub fn is_better_than<Q>(&self, other: &ScoredPoint<Q>, distance: Distance) -> bool {
+        distance.is_better(self.score, other.score)
+    }
+}
 
-pub struct Qdrant {
-    pub url_base: String,
-    api_key: Option<String>,
+/// Two points and their similarity, returned by `search_matrix`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatrixPair {
+    pub a: PointId,
+    pub b: PointId,
+    pub score: f32,
 }
 
-impl Qdrant {
-    pub fn new_with_url(url_base_: String) -> Qdrant {
-        Qdrant {
-            url_base: url_base_,
-            api_key: None,
+/// A point found by `search_multi`, with the collection it comes from
+#[derive(Debug)]
+pub struct MultiSearchHit<P = Map<String, Value>> {
+    pub collection: String,
+    pub point: ScoredPoint<P>,
+}
+
+/// The points sharing a value of the `group_by` payload field, returned by `search_groups`.
+/// `P` is the payload type of the hits and `L` the one of the lookup point, which comes from another collection.
+#[derive(Debug, Deserialize)]
+pub struct PointGroup<P = Map<String, Value>, L = Map<String, Value>> {
+    /// The value of the `group_by` field, a string or an integer
+    pub id: Value,
+
+    /// The best points of the group, best first
+    pub hits: Vec<ScoredPoint<P>>,
+
+    /// The point of the lookup collection whose id is the value of the group, if `with_lookup` was set
+    pub lookup: Option<Point<L>>,
+}
+
+/// Looks up a point of another collection for each group of a grouped search,
+/// e.g. the document of chunks grouped by `doc_id`
+#[derive(Debug, Clone, Serialize)]
+pub struct WithLookup {
+    /// The collection where the point whose id is the value of the group is looked up
+    pub collection: String,
+    /// Which payload of the looked up point to return. Defaults to the whole payload.
+    pub with_payload: PayloadSelector,
+    /// Whether to return the vectors of the looked up point. Defaults to `false`.
+    pub with_vectors: bool,
+}
+
+impl WithLookup {
+    pub fn new(collection: impl Into<String>) -> WithLookup {
+        WithLookup {
+            collection: collection.into(),
+            with_payload: PayloadSelector::All,
+            with_vectors: false,
         }
     }
+}
 
-    pub fn new() -> Qdrant {
-        Qdrant::new_with_url("http://localhost:6333".to_string())
+/// Selects which payload of the points is returned
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum PayloadSelector {
+    /// The whole payload
+    #[default]
+    All,
+    /// No payload
+    None,
+    /// Only the given payload keys
+    Include(Vec<String>),
+    /// All the payload keys except the given ones
+    Exclude(Vec<String>),
+}
+impl Serialize for PayloadSelector {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            PayloadSelector::All => serializer.serialize_bool(true),
+            PayloadSelector::None => serializer.serialize_bool(false),
+            PayloadSelector::Include(keys) => keys.serialize(serializer),
+            PayloadSelector::Exclude(keys) => json!({ "exclude": keys }).serialize(serializer),
+        }
     }
+}
 
-    pub fn set_api_key(&mut self, api_key: impl Into<String>) {
-        self.api_key = Some(api_key.into());
+/// How many replicas must answer a read in a distributed deployment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadConsistency {
+    /// The given number of replicas
+    Factor(u64),
+    /// A majority of the replicas
+    Majority,
+    /// More than half of the replicas
+    Quorum,
+    /// All the replicas
+    All,
+}
+impl Display for ReadConsistency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadConsistency::Factor(factor) => write!(f, "{}", factor),
+            ReadConsistency::Majority => write!(f, "majority"),
+            ReadConsistency::Quorum => write!(f, "quorum"),
+            ReadConsistency::All => write!(f, "all"),
+        }
     }
 }
 
-impl Default for Qdrant {
-    fn default() -> Self {
-        Self::new()
+/// The ordering guarantee of a write in a distributed deployment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteOrdering {
+    /// Writes may be applied out of order. The default.
+    Weak,
+    /// Writes go through a dynamically elected leader
+    Medium,
+    /// Writes go through the permanent leader, so they are applied in order
+    Strong,
+}
+impl Display for WriteOrdering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WriteOrdering::Weak => write!(f, "weak"),
+            WriteOrdering::Medium => write!(f, "medium"),
+            WriteOrdering::Strong => write!(f, "strong"),
+        }
     }
 }
 
-/// Shortcut functions
-impl Qdrant {
-    /// Shortcut functions
-    pub async fn collection_info(&self, collection_name: &str) -> u64 {
-        #[cfg(feature = "logging")]
-        info!(target: "stdout", "get collection info: '{}'", collection_name);
-
-        let v = self.collection_info_api(collection_name).await.unwrap();
-        v.get("result")
-            .unwrap()
-            .get("points_count")
-            .unwrap()
-            .as_u64()
-            .unwrap()
+/// The options of the write operations, e.g. `upsert_points_with_options`
+#[derive(Debug, Clone, Default)]
+pub struct WriteOptions {
+    /// The write ordering. Qdrant uses `Weak` if unset.
+    pub ordering: Option<WriteOrdering>,
+    /// The shards to write to, for collections using custom sharding.
+    /// It applies to the whole request, e.g. all the points of an upsert land on the shard of a tenant.
+    pub shard_key: Option<ShardKeySelector>,
+    /// Upserts only: checks the sizes of the vectors against the collection config before sending them,
+    /// at the cost of fetching the collection info first. Defaults to `false`.
+    pub check_vector_sizes: bool,
+}
+impl WriteOptions {
+    fn query(&self) -> Vec<(&'static str, String)> {
+        match self.ordering {
+            Some(ordering) => vec![("ordering", ordering.to_string())],
+            None => vec![],
+        }
     }
 
-    pub async fn create_collection(&self, collection_name: &str, size: u32) -> Result<(), Error> {
-        #[cfg(feature = "logging")]
-        info!(target: "stdout", "create collection '{}'", collection_name);
-
-        match self.collection_exists(collection_name).await {
-            Ok(false) => (),
-            Ok(true) => {
-                let err_msg = format!("Collection '{}' already exists", collection_name);
+    /// Adds the options that go in the request body to the params
+    fn add_to(&self, params: &mut Value) {
+        if let Some(shard_key) = &self.shard_key {
+            params["shard_key"] = json!(shard_key);
+        }
+    }
+}
 
-                #[cfg(feature = "logging")]
-                error!(target: "stdout", "{}", &err_msg);
+/// Search settings of a single request, trading recall for latency
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SearchParams {
+    /// The size of the HNSW candidate list. Larger is more accurate but slower.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hnsw_ef: Option<u64>,
+    /// Searches exhaustively instead of using the index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exact: Option<bool>,
+    /// Only searches the segments that are already indexed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexed_only: Option<bool>,
+}
 
-                bail!(err_msg);
-            }
-            Err(e) => {
-                #[cfg(feature = "logging")]
-                error!(target: "stdout", "{}", e);
+/// The options of `search_points_with_options`
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    /// Only return the points scoring at least this
+    pub score_threshold: Option<f32>,
+    /// Only search the points matching this filter
+    pub filter: Option<Value>,
+    /// Which payload to return. Defaults to the whole payload.
+    pub with_payload: PayloadSelector,
+    /// Whether to return the vectors. Defaults to `true`.
+    ///
+    /// Qdrant's REST API only returns vectors as JSON arrays of numbers; there is no compact binary format.
+    /// To shrink large responses, enable `QdrantBuilder::gzip`, or leave the vectors out when they are not needed.
+    pub with_vector: bool,
+    /// The read consistency
+    pub consistency: Option<ReadConsistency>,
+    /// The shards to search, for collections using custom sharding. All shards are searched if unset.
+    pub shard_key: Option<ShardKeySelector>,
+    /// The search settings of this request
+    pub params: Option<SearchParams>,
+    /// The number of best points to skip, for paging through the results
+    pub offset: Option<u64>,
+    /// The named vector space to search, required for collections with named vectors.
+    /// The unnamed vector space is searched if unset.
+    pub using: Option<String>,
+    /// The time in seconds Qdrant may spend on the search, independent of the HTTP client timeout.
+    /// Qdrant fails the search if it takes longer. Sent in the query string, as Qdrant expects it.
+    pub timeout: Option<u64>,
+}
 
-                bail!("{}", e);
-            }
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            score_threshold: None,
+            filter: None,
+            with_payload: PayloadSelector::All,
+            with_vector: true,
+            consistency: None,
+            shard_key: None,
+            params: None,
+            offset: None,
+            using: None,
+            timeout: None,
         }
+    }
+}
 
-        let params = json!({
-            "vectors": {
-                "size": size,
-                "distance": "Cosine",
-                "on_disk": true,
-            }
+impl SearchOptions {
+    /// The body of the search request sent with these options, e.g. to log it or to check a filter
+    /// without running the search. The read consistency and the timeout are not part of it, as they are sent in the query string.
+    pub fn request_body(&self, vector: Vec<f32>, limit: u64) -> Value {
+        let vector = match &self.using {
+            Some(name) => json!({ "name": name, "vector": vector }),
+            None => json!(vector),
+        };
+        let mut params = json!({
+            "vector": vector,
+            "limit": limit,
+            "with_payload": self.with_payload,
+            "with_vector": self.with_vector,
         });
-        if !self.create_collection_api(collection_name, &params).await? {
-            bail!("Failed to create collection '{}'", collection_name);
+        if let Some(score_threshold) = self.score_threshold {
+            params["score_threshold"] = json!(score_threshold);
         }
-        Ok(())
+        if let Some(filter) = &self.filter {
+            params["filter"] = filter.clone();
+        }
+        if let Some(shard_key) = &self.shard_key {
+            params["shard_key"] = json!(shard_key);
+        }
+        if let Some(search_params) = &self.params {
+            params["params"] = json!(search_params);
+        }
+        if let Some(offset) = self.offset {
+            params["offset"] = json!(offset);
+        }
+
+        params
+    }
+    /// The query parameters of the search request: the read consistency and the timeout
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = consistency_query(self.consistency);
+        if let Some(timeout) = self.timeout {
+            query.push(("timeout", timeout.to_string()));
+        }
+        query
     }
+}
 
-    pub async fn list_collections(&self) -> Result<Vec<String>, Error> {
-        #[cfg(feature = "logging")]
-        info!(target: "stdout", "list collections");
+/// The options of `get_points_with_options`
+#[derive(Debug, Clone)]
+pub struct GetPointsOptions {
+    /// Which payload to return. Defaults to the whole payload.
+    pub with_payload: PayloadSelector,
+    /// Whether to return the vectors. Defaults to `true`. Vectors come back as JSON arrays, see `SearchOptions::with_vector`.
+    pub with_vector: bool,
+    /// The read consistency
+    pub consistency: Option<ReadConsistency>,
+}
 
-        self.list_collections_api().await
+impl Default for GetPointsOptions {
+    fn default() -> Self {
+        GetPointsOptions {
+            with_payload: PayloadSelector::All,
+            with_vector: true,
+            consistency: None,
+        }
     }
+}
 
-    pub async fn collection_exists(&self, collection_name: &str) -> Result<bool, Error> {
-        #[cfg(feature = "logging")]
-        info!(target: "stdout", "check collection existence: {}", collection_name);
-
-        let collection_names = self.list_collections().await?;
+/// The options of `scroll_points`
+#[derive(Debug, Clone, Serialize)]
+pub struct ScrollOptions {
+    /// Only scroll the points matching this filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Value>,
+    /// The maximum number of points in a page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    /// The id to start the page from, as returned in `next_page_offset`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<PointId>,
+    /// Which payload to return. Defaults to the whole payload.
+    pub with_payload: PayloadSelector,
+    /// Whether to return the vectors. Defaults to `true`. Vectors come back as JSON arrays, see `SearchOptions::with_vector`.
+    pub with_vector: bool,
+    /// Orders the points by a payload field instead of by id.
+    /// Requires a range index on the field, and cannot be combined with `offset`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_by: Option<OrderBy>,
+    /// The read consistency
+    #[serde(skip)]
+    pub consistency: Option<ReadConsistency>,
+}
 
-        Ok(collection_names.contains(&collection_name.to_string()))
+impl Default for ScrollOptions {
+    fn default() -> Self {
+        ScrollOptions {
+            filter: None,
+            limit: None,
+            offset: None,
+            with_payload: PayloadSelector::All,
+            with_vector: true,
+            order_by: None,
+            consistency: None,
+        }
     }
+}
 
-    pub async fn delete_collection(&self, collection_name: &str) -> Result<(), Error> {
-        #[cfg(feature = "logging")]
-        info!(target: "stdout", "delete collection '{}'", collection_name);
+/// The order of a scroll by the numeric or datetime payload field `key`
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderBy {
+    pub key: String,
+    /// Defaults to ascending
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
+    /// The value of the field to start from, e.g. `json!(1700000000)` or `json!("2024-01-01T00:00:00Z")`.
+    /// Qdrant returns no `next_page_offset` for ordered scrolls, so the next page can start from the last value seen;
+    /// points sharing that value are then returned again.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_from: Option<Value>,
+}
 
-        match self.collection_exists(collection_name).await {
-            Ok(true) => (),
-            Ok(false) => {
-                let err_msg = format!("Not found collection '{}'", collection_name);
+impl OrderBy {
+    pub fn new(key: impl Into<String>, direction: Direction) -> OrderBy {
+        OrderBy {
+            key: key.into(),
+            direction: Some(direction),
+            start_from: None,
+        }
+    }
+}
 
-                #[cfg(feature = "logging")]
-                error!(target: "stdout", "{}", &err_msg);
+/// The direction of an `OrderBy`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Asc,
+    Desc,
+}
 
-                bail!(err_msg);
-            }
-            Err(e) => {
-                #[cfg(feature = "logging")]
-                error!(target: "stdout", "{}", e);
+/// The request of `retrieve_points`
+#[derive(Debug, Clone)]
+pub struct RetrieveRequest {
+    /// Only retrieve the points with these ids. Points with any id are retrieved if empty.
+    pub ids: Vec<PointId>,
+    /// Only retrieve the points matching this filter
+    pub filter: Option<Value>,
+    /// The maximum number of points in a page, for filtered retrievals
+    pub limit: Option<u64>,
+    /// The id to start the page from, as returned in `next_page_offset`
+    pub offset: Option<PointId>,
+    /// Which payload to return. Defaults to the whole payload.
+    pub with_payload: PayloadSelector,
+    /// Whether to return the vectors. Defaults to `true`.
+    pub with_vector: bool,
+    /// The read consistency
+    pub consistency: Option<ReadConsistency>,
+}
 
-                bail!("{}", e);
-            }
+impl Default for RetrieveRequest {
+    fn default() -> Self {
+        RetrieveRequest {
+            ids: vec![],
+            filter: None,
+            limit: None,
+            offset: None,
+            with_payload: PayloadSelector::All,
+            with_vector: true,
+            consistency: None,
         }
+    }
+}
 
-        if !self.delete_collection_api(collection_name).await? {
-            bail!("Failed to delete collection '{}'", collection_name);
-        }
-        Ok(())
+impl ScrollOptions {
+    /// The body of the scroll request sent with these options, without the read consistency sent in the query string
+    pub fn request_body(&self) -> Value {
+        json!(self)
     }
+}
 
-    pub async fn upsert_points(
-        &self,
-        collection_name: &str,
-        points: Vec<Point>,
-    ) -> Result<(), Error> {
-        #[cfg(feature = "logging")]
-        info!(target: "stdout", "upsert {} points to collection '{}'", points.len(), collection_name);
+/// A successful Qdrant response: the `result` of the operation, with the `status`
+/// and the `time` in seconds Qdrant spent on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QdrantResponse<T> {
+    pub result: T,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub time: f64,
+}
 
-        let params = json!({
-            "points": points,
-        });
-        self.upsert_points_api(collection_name, &params).await
-    }
+/// The `result` of grouped searches and recommendations
+#[derive(Deserialize)]
+struct GroupsResult<P, L = Map<String, Value>> {
+    groups: Vec<PointGroup<P, L>>,
+}
 
-    pub async fn search_points(
-        &self,
-        collection_name: &str,
-        vector: Vec<f32>,
-        limit: u64,
-        score_threshold: Option<f32>,
-    ) -> Result<Vec<ScoredPoint>, Error> {
-        #[cfg(feature = "logging")]
-        info!(target: "stdout", "search points in collection '{}'", collection_name);
+/// The `result` of `query_points`
+#[derive(Deserialize)]
+struct PointsResult<P> {
+    points: Vec<ScoredPoint<P>>,
+}
 
-        let score_threshold = score_threshold.unwrap_or(0.0);
+/// The `result` of `search_matrix`
+#[derive(Deserialize)]
+struct MatrixPairsResult {
+    pairs: Vec<MatrixPair>,
+}
 
-        let params = json!({
-            "vector": vector,
-            "limit": limit,
-            "with_payload": true,
-            "with_vector": true,
-            "score_threshold": score_threshold,
-        });
+/// The `result` of `facet`
+#[derive(Deserialize)]
+struct FacetResult {
+    hits: Vec<FacetHit>,
+}
 
-        match self.search_points_api(collection_name, &params).await {
-            Ok(v) => {
-                match v.get("result") {
-                    Some(v) => match v.as_array() {
-                        Some(rs) => {
-                            let mut sps: Vec<ScoredPoint> = Vec::<ScoredPoint>::new();
-                            for r in rs {
-                                let sp: ScoredPoint = serde_json::from_value(r.clone()).unwrap();
-                                sps.push(sp);
-                            }
-                            Ok(sps)
-                        }
-                        None => {
-                            bail!("[qdrant] The value corresponding to the 'result' key is not an array.")
-                        }
-                    },
-                    None => {
-                        let warn_msg = "[qdrant] The given key 'result' does not exist.";
-
-                        #[cfg(feature = "logging")]
-                        warn!(target: "stdout", "{}", warn_msg);
-
-                        Ok(vec![])
-                    }
-                }
-            }
-            Err(e) => {
-                let warn_msg = format!("[qdrant] Failed to search points: {}", e);
+#[derive(Deserialize)]
+struct FacetHit {
+    value: Value,
+    count: u64,
+}
 
-                #[cfg(feature = "logging")]
-                warn!(target: "stdout", "{}", warn_msg);
+/// The part of the collection info `collection_info` reads
+#[derive(Deserialize)]
+struct PointsCount {
+    points_count: u64,
+}
+
+/// A page of points returned by `scroll_points`
+#[derive(Debug, Deserialize)]
+pub struct ScrollResult<P = Map<String, Value>> {
+    pub points: Vec<Point<P>>,
+    /// The offset of the next page, `None` on the last page
+    pub next_page_offset: Option<PointId>,
+}
 
-                Ok(vec![])
+/// The new vectors of a point, used by `PointOperation::UpdateVectors`
+#[derive(Debug, Serialize)]
+pub struct PointVectors {
+    pub id: PointId,
+    /// The vectors to set. With named vectors, the vectors not given are left unchanged.
+    pub vector: VectorData,
+}
+
+/// An operation of `batch_update`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PointOperation<P = Map<String, Value>> {
+    /// Inserts the points, or replaces the points with the same ids
+    Upsert { points: Vec<Point<P>> },
+    /// Deletes the points
+    Delete { points: Vec<PointId> },
+    /// Deletes the points matching the filter
+    #[serde(rename = "delete")]
+    DeleteByFilter { filter: Value },
+    /// Sets the given payload keys of the points, leaving the other keys unchanged
+    SetPayload {
+        payload: Map<String, Value>,
+        points: Vec<PointId>,
+    },
+    /// Replaces the whole payload of the points
+    OverwritePayload {
+        payload: Map<String, Value>,
+        points: Vec<PointId>,
+    },
+    /// Deletes the given payload keys of the points
+    DeletePayload {
+        keys: Vec<String>,
+        points: Vec<PointId>,
+    },
+    /// Deletes the whole payload of the points
+    ClearPayload { points: Vec<PointId> },
+    /// Replaces vectors of existing points
+    UpdateVectors { points: Vec<PointVectors> },
+    /// Deletes the named vectors of the points
+    DeleteVectors {
+        points: Vec<PointId>,
+        vector: Vec<String>,
+    },
+}
+
+impl<P> PointOperation<P> {
+    /// The ids of the points the operation writes to
+    fn point_ids(&self) -> Vec<&PointId> {
+        match self {
+            PointOperation::Upsert { points } => points.iter().map(|point| &point.id).collect(),
+            PointOperation::UpdateVectors { points } => {
+                points.iter().map(|point| &point.id).collect()
             }
+            PointOperation::Delete { points }
+            | PointOperation::SetPayload { points, .. }
+            | PointOperation::OverwritePayload { points, .. }
+            | PointOperation::DeletePayload { points, .. }
+            | PointOperation::ClearPayload { points }
+            | PointOperation::DeleteVectors { points, .. } => points.iter().collect(),
+            PointOperation::DeleteByFilter { .. } => vec![],
         }
     }
+}
 
-    pub async fn get_points(&self, collection_name: &str, ids: &[PointId]) -> Vec<Point> {
-        #[cfg(feature = "logging")]
+/// The outcome of `upsert_points_iter`
+#[derive(Debug, Default)]
+pub struct UpsertSummary {
+    /// The number of points written
+    pub upserted: u64,
+    /// The chunks that failed, as the position of their first point in the iterator and the error
+    pub errors: Vec<(u64, QdrantError)>,
+}
+
+/// The data type of a payload field index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadSchemaType {
+    Keyword,
+    Integer,
+    Float,
+    Geo,
+    Text,
+    Bool,
+    Datetime,
+    Uuid,
+}
+
+/// The distance function used to compare vectors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Distance {
+    Cosine,
+    Euclid,
+    Dot,
+    Manhattan,
+}
+impl Distance {
+    /// Whether higher scores mean closer vectors.
+    /// Cosine and Dot scores are similarities, Euclid and Manhattan scores are distances.
+    pub fn higher_is_better(&self) -> bool {
+        matches!(self, Distance::Cosine | Distance::Dot)
+    }
+
+    /// Whether the score `a` is strictly better than the score `b` with this distance
+    pub fn is_better(&self, a: f32, b: f32) -> bool {
+        match self.higher_is_better() {
+            true => a > b,
+            false => a < b,
+        }
+    }
+}
+
+/// The status of a collection.
+/// `Green` means all segments are ready, `Yellow` means optimizations are running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CollectionStatus {
+    Green,
+    Yellow,
+    Grey,
+    Red,
+}
+
+/// The data type the values of stored vectors are converted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorDataType {
+    Float32,
+    /// Half the storage of `Float32`, with a small loss of precision
+    Float16,
+    /// A quarter of the storage of `Float32`. The values must already be integers in 0..=255.
+    Uint8,
+}
+
+/// The params of a dense vector space
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VectorParams {
+    /// Size of the vectors
+    pub size: u64,
+
+    /// Distance function used to compare the vectors
+    pub distance: Distance,
+
+    /// Whether the vectors are stored on disk instead of in RAM
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+
+    /// The data type of the stored vectors. Qdrant uses `Float32` if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datatype: Option<VectorDataType>,
+}
+
+/// The vectors config of a collection, either a single unnamed vector space or a set of named ones
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum VectorsConfig {
+    Single(VectorParams),
+    Multi(HashMap<String, VectorParams>),
+}
+
+impl Default for VectorsConfig {
+    fn default() -> Self {
+        VectorsConfig::Multi(HashMap::new())
+    }
+}
+
+/// The params of a sparse vector index
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SparseIndexParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_scan_threshold: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+/// A modifier applied to the values of sparse vectors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Modifier {
+    None,
+    /// Inverse document frequency, used for BM25-style scoring
+    Idf,
+}
+
+/// The params of a sparse vector space
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SparseVectorParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<SparseIndexParams>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modifier: Option<Modifier>,
+}
+
+/// The params a collection was created with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionParams {
+    #[serde(default)]
+    pub vectors: VectorsConfig,
+    pub sparse_vectors: Option<HashMap<String, SparseVectorParams>>,
+    pub shard_number: Option<u32>,
+    pub replication_factor: Option<u32>,
+    pub write_consistency_factor: Option<u32>,
+    pub on_disk_payload: Option<bool>,
+}
+
+/// The configuration of a collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionConfig {
+    pub params: CollectionParams,
+    #[serde(default)]
+    pub hnsw_config: Value,
+    #[serde(default)]
+    pub optimizer_config: Value,
+    #[serde(default)]
+    pub wal_config: Value,
+    pub quantization_config: Option<Value>,
+}
+
+/// The complete description of a collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionInfo {
+    pub status: CollectionStatus,
+
+    /// `"ok"`, or an object with the error of the optimizer
+    pub optimizer_status: Value,
+
+    pub vectors_count: Option<u64>,
+    pub indexed_vectors_count: Option<u64>,
+    pub points_count: Option<u64>,
+    pub segments_count: u64,
+    pub config: CollectionConfig,
+
+    /// The payload fields that are indexed, with their schema
+    #[serde(default)]
+    pub payload_schema: Map<String, Value>,
+}
+
+impl CollectionInfo {
+    /// The share of the dense vectors that are indexed, from 0.0 to 1.0, e.g. for an "n% indexed" gauge.
+    /// `None` if Qdrant did not report the counts. An empty collection counts as fully indexed.
+    ///
+    /// Qdrant counts indexed vectors, not points, so the count is compared to the points count times
+    /// the number of dense vector spaces. Points missing some of their named vectors make the ratio lower.
+    ///
+    /// Note that Qdrant does not index segments below the `indexing_threshold` of the optimizer,
+    /// so small collections may stay below 1.0 while being searchable.
+    pub fn indexed_ratio(&self) -> Option<f64> {
+        let points_count = self.points_count?;
+        let indexed_vectors_count = self.indexed_vectors_count?;
+        let spaces = match &self.config.params.vectors {
+            VectorsConfig::Single(_) => 1,
+            VectorsConfig::Multi(spaces) => spaces.len() as u64,
+        };
+        let vectors_count = points_count * spaces;
+        if vectors_count == 0 {
+            return Some(1.0);
+        }
+        Some(indexed_vectors_count as f64 / vectors_count as f64)
+    }
+}
+
+/// How the points of a collection are distributed among its shards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShardingMethod {
+    /// Points are distributed by their ids
+    Auto,
+    /// Points are routed to the shards of the shard key given when writing them
+    Custom,
+}
+
+/// The key of a shard of a collection using custom sharding, e.g. a tenant id
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ShardKey {
+    Keyword(String),
+    Number(u64),
+}
+impl From<String> for ShardKey {
+    fn from(keyword: String) -> Self {
+        ShardKey::Keyword(keyword)
+    }
+}
+impl From<&str> for ShardKey {
+    fn from(keyword: &str) -> Self {
+        ShardKey::Keyword(keyword.to_string())
+    }
+}
+impl From<u64> for ShardKey {
+    fn from(number: u64) -> Self {
+        ShardKey::Number(number)
+    }
+}
+
+/// Selects the shards a request applies to by one or more shard keys
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum ShardKeySelector {
+    One(ShardKey),
+    Many(Vec<ShardKey>),
+}
+impl From<ShardKey> for ShardKeySelector {
+    fn from(key: ShardKey) -> Self {
+        ShardKeySelector::One(key)
+    }
+}
+impl From<Vec<ShardKey>> for ShardKeySelector {
+    fn from(keys: Vec<ShardKey>) -> Self {
+        ShardKeySelector::Many(keys)
+    }
+}
+
+/// The state of the cluster, returned by `cluster_info`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterInfo {
+    /// `"enabled"` in distributed mode, `"disabled"` otherwise
+    pub status: String,
+
+    /// The id of the peer that answered
+    pub peer_id: Option<u64>,
+
+    /// The peers of the cluster, by peer id
+    #[serde(default)]
+    pub peers: HashMap<u64, PeerInfo>,
+
+    /// The state of the consensus
+    #[serde(default)]
+    pub raft_info: Value,
+}
+
+/// A peer of the cluster
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerInfo {
+    pub uri: String,
+}
+
+/// The state of a shard replica
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplicaState {
+    Active,
+    Dead,
+    Partial,
+    Initializing,
+    Listener,
+    PartialSnapshot,
+    Recovery,
+    Resharding,
+    /// A state unknown to this client
+    #[serde(other)]
+    Other,
+}
+
+/// A shard stored on the peer that answered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalShardInfo {
+    pub shard_id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    pub points_count: u64,
+    pub state: ReplicaState,
+}
+
+/// A shard stored on another peer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteShardInfo {
+    pub shard_id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    pub peer_id: u64,
+    pub state: ReplicaState,
+}
+
+/// A shard being moved or replicated between peers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardTransferInfo {
+    pub shard_id: u32,
+    /// The source peer
+    pub from: u64,
+    /// The destination peer
+    pub to: u64,
+    /// `true` if the shard is replicated, `false` if it is moved
+    pub sync: bool,
+}
+
+/// The distribution of the shards of a collection, returned by `collection_cluster_info`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionClusterInfo {
+    /// The id of the peer that answered
+    pub peer_id: u64,
+    pub shard_count: u64,
+    pub local_shards: Vec<LocalShardInfo>,
+    pub remote_shards: Vec<RemoteShardInfo>,
+    #[serde(default)]
+    pub shard_transfers: Vec<ShardTransferInfo>,
+}
+
+/// A snapshot stored by Qdrant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotDescription {
+    /// The file name of the snapshot
+    pub name: String,
+    pub creation_time: Option<String>,
+    /// The size in bytes
+    pub size: u64,
+    /// The SHA-256 checksum of the file
+    pub checksum: Option<String>,
+}
+
+/// Where a collection is recovered from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotSource {
+    /// A location Qdrant fetches the snapshot from itself: an `http(s)://` url,
+    /// or a `file://` url of a file on the Qdrant node
+    Location(String),
+    /// A snapshot file on the client machine, uploaded to Qdrant
+    File(std::path::PathBuf),
+}
+
+/// Strings with a scheme, e.g. `https://...` or `file:///...`, are locations; anything else is a local file path
+impl From<&str> for SnapshotSource {
+    fn from(source: &str) -> Self {
+        match source.contains("://") {
+            true => SnapshotSource::Location(source.to_string()),
+            false => SnapshotSource::File(source.into()),
+        }
+    }
+}
+
+impl From<String> for SnapshotSource {
+    fn from(source: String) -> Self {
+        SnapshotSource::from(source.as_str())
+    }
+}
+
+impl From<std::path::PathBuf> for SnapshotSource {
+    fn from(path: std::path::PathBuf) -> Self {
+        SnapshotSource::File(path)
+    }
+}
+
+/// The largest vector size Qdrant accepts
+const MAX_VECTOR_SIZE: u64 = 65536;
+
+/// The options of a new collection
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateCollectionOptions {
+    /// The dense vector spaces of the collection
+    pub vectors: VectorsConfig,
+
+    /// The named sparse vector spaces of the collection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sparse_vectors: Option<HashMap<String, SparseVectorParams>>,
+
+    /// The sharding method. Qdrant uses `Auto` if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sharding_method: Option<ShardingMethod>,
+
+    /// The name of an existing collection whose points are copied into the new one
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_init_from"
+    )]
+    pub init_from: Option<String>,
+}
+
+impl CreateCollectionOptions {
+    /// Options for a collection with a single unnamed vector space of the given size,
+    /// compared with the Cosine distance and stored on disk
+    pub fn new(size: u64) -> CreateCollectionOptions {
+        CreateCollectionOptions {
+            vectors: VectorsConfig::Single(VectorParams {
+                size,
+                distance: Distance::Cosine,
+                on_disk: Some(true),
+                datatype: None,
+            }),
+            sparse_vectors: None,
+            sharding_method: None,
+            init_from: None,
+        }
+    }
+
+    /// Checks the sizes of the vector spaces before they are sent to Qdrant
+    fn validate(&self) -> Result<(), QdrantError> {
+        let spaces: Vec<&VectorParams> = match &self.vectors {
+            VectorsConfig::Single(params) => vec![params],
+            VectorsConfig::Multi(spaces) => spaces.values().collect(),
+        };
+        for params in spaces {
+            if params.size == 0 {
+                return Err(QdrantError::InvalidInput(
+                    "vector size must be > 0".to_string(),
+                ));
+            }
+            if params.size > MAX_VECTOR_SIZE {
+                return Err(QdrantError::InvalidInput(format!(
+                    "vector size {} exceeds the maximum of {}. Is it the number of points instead of the dimension?",
+                    params.size, MAX_VECTOR_SIZE
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets whether the vectors of every dense vector space are stored on disk (`true`, the default)
+    /// or kept in RAM (`false`) for lower latency
+    pub fn on_disk(mut self, on_disk: bool) -> Self {
+        match &mut self.vectors {
+            VectorsConfig::Single(params) => params.on_disk = Some(on_disk),
+            VectorsConfig::Multi(spaces) => {
+                for params in spaces.values_mut() {
+                    params.on_disk = Some(on_disk);
+                }
+            }
+        }
+        self
+    }
+}
+
+/// Optimizer settings to change on an existing collection. Unset fields are left unchanged.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OptimizersConfigDiff {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_threshold: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vacuum_min_vector_number: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_segment_number: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_segment_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memmap_threshold: Option<u64>,
+    /// Segments larger than this (in kilobytes) get a vector index. `0` disables indexing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_threshold: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flush_interval_sec: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_optimization_threads: Option<u64>,
+}
+
+/// HNSW index settings to change on an existing collection. Unset fields are left unchanged.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HnswConfigDiff {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub m: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ef_construct: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_scan_threshold: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_indexing_threads: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_m: Option<u64>,
+}
+
+/// Collection params to change on an existing collection. Unset fields are left unchanged.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CollectionParamsDiff {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replication_factor: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_consistency_factor: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_fan_out_factor: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_disk_payload: Option<bool>,
+}
+
+/// The settings that can be changed on an existing collection
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UpdateCollectionOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optimizers_config: Option<OptimizersConfigDiff>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<CollectionParamsDiff>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hnsw_config: Option<HnswConfigDiff>,
+    /// The raw quantization config, e.g. `{"scalar": {"type": "int8"}}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantization_config: Option<Value>,
+}
+
+/// A vector, or the id of a stored point whose vector is used instead
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum VectorInput {
+    Dense(Vec<f32>),
+    Sparse(SparseVector),
+    Id(PointId),
+}
+impl From<Vec<f32>> for VectorInput {
+    fn from(vector: Vec<f32>) -> Self {
+        VectorInput::Dense(vector)
+    }
+}
+impl From<SparseVector> for VectorInput {
+    fn from(vector: SparseVector) -> Self {
+        VectorInput::Sparse(vector)
+    }
+}
+impl From<PointId> for VectorInput {
+    fn from(id: PointId) -> Self {
+        VectorInput::Id(id)
+    }
+}
+
+/// How the positive and negative examples of a recommend query are combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendStrategy {
+    AverageVector,
+    BestScore,
+    SumScores,
+}
+
+/// The examples of a recommend query
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RecommendInput {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub positive: Vec<VectorInput>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub negative: Vec<VectorInput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<RecommendStrategy>,
+}
+
+/// Where the vectors of the example point ids of a recommend request are looked up
+#[derive(Debug, Clone, Serialize)]
+pub struct LookupLocation {
+    /// The collection holding the example points
+    pub collection: String,
+    /// The name of the vector to use. Defaults to the `using` vector of the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector: Option<String>,
+}
+
+/// A request to the recommend API (`/points/recommend`)
+#[derive(Debug, Clone, Serialize)]
+pub struct RecommendRequest {
+    /// The ids or vectors of the examples to get closer to
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub positive: Vec<VectorInput>,
+    /// The ids or vectors of the examples to get away from
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub negative: Vec<VectorInput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<RecommendStrategy>,
+    /// Name of the vector to recommend with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub using: Option<String>,
+    /// Looks up the example point ids in another collection. Defaults to the searched collection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lookup_from: Option<LookupLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_threshold: Option<f32>,
+    pub limit: u64,
+    pub with_payload: bool,
+    pub with_vector: bool,
+}
+
+impl Default for RecommendRequest {
+    fn default() -> Self {
+        RecommendRequest {
+            positive: Vec::new(),
+            negative: Vec::new(),
+            strategy: None,
+            using: None,
+            lookup_from: None,
+            filter: None,
+            score_threshold: None,
+            limit: 10,
+            with_payload: true,
+            with_vector: true,
+        }
+    }
+}
+
+/// The method used to merge the results of several prefetches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Fusion {
+    /// Reciprocal rank fusion
+    Rrf,
+    /// Distribution-based score fusion
+    Dbsf,
+}
+
+/// A query of the universal query API
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Query {
+    /// Finds the points nearest to a vector
+    Nearest(VectorInput),
+    /// Recommends points using positive and negative examples
+    Recommend(RecommendInput),
+    /// Merges the results of the prefetches
+    Fusion(Fusion),
+}
+
+/// A sub-request whose results are used as candidates by the main query
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Prefetch {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub prefetch: Vec<Prefetch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<Query>,
+    /// Name of the vector to query against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub using: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_threshold: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+/// A request to the universal query API (`/points/query`)
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryRequest {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub prefetch: Vec<Prefetch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<Query>,
+    /// Name of the vector to query against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub using: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_threshold: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// The search settings of this request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<SearchParams>,
+    pub with_payload: bool,
+    pub with_vector: bool,
+}
+
+impl Default for QueryRequest {
+    fn default() -> Self {
+        QueryRequest {
+            prefetch: Vec::new(),
+            query: None,
+            using: None,
+            filter: None,
+            score_threshold: None,
+            limit: None,
+            offset: None,
+            params: None,
+            with_payload: true,
+            with_vector: true,
+        }
+    }
+}
+
+pub struct Qdrant {
+    pub url_base: String,
+    api_key: Option<String>,
+    bearer_token: Option<String>,
+    retries: u32,
+    gzip: bool,
+    max_body_size: Option<usize>,
+    client: reqwest::Client,
+    /// The point counts fetched by `collection_info_cached`, with the time they were fetched
+    info_cache: Mutex<HashMap<String, (Instant, u64)>>,
+}
+
+impl Qdrant {
+    /// Creates a client for the Qdrant service at the given url.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying HTTP client cannot be initialized, like `reqwest::Client::new()`.
+    pub fn new_with_url(url_base_: String) -> Qdrant {
+        QdrantBuilder::new()
+            .url(url_base_)
+            .build()
+            .expect("[qdrant] Failed to build the HTTP client")
+    }
+
+    pub fn new() -> Qdrant {
+        Qdrant::new_with_url("http://localhost:6333".to_string())
+    }
+
+    /// Creates a client for the Qdrant service at the given url that sends its requests with `client`
+    pub fn with_client(url_base: impl Into<String>, client: reqwest::Client) -> Qdrant {
+        QdrantBuilder::new()
+            .url(url_base)
+            .client(client)
+            .build()
+            .expect("[qdrant] Failed to build the client")
+    }
+
+    /// Returns a builder for configuring the client in one go
+    pub fn builder() -> QdrantBuilder {
+        QdrantBuilder::new()
+    }
+
+    pub fn set_api_key(&mut self, api_key: impl Into<String>) {
+        self.api_key = Some(api_key.into());
+    }
+
+    /// Creates a request with the authentication headers attached
+    fn request(&self, method: Method, url: &str) -> reqwest::RequestBuilder {
+        let mut builder = self.client.request(method, url);
+        if let Some(api_key) = &self.api_key {
+            builder = builder.header("api-key", api_key);
+        }
+        if let Some(token) = &self.bearer_token {
+            builder = builder.bearer_auth(token);
+        }
+        builder
+    }
+
+    /// Sets the JSON body of a request, compressing it if gzip is enabled.
+    /// `Content-Type` is only sent with a body, as some proxies reject it on bodyless requests.
+    fn with_body(
+        &self,
+        builder: reqwest::RequestBuilder,
+        body: Vec<u8>,
+    ) -> reqwest::RequestBuilder {
+        let builder = builder.header("Content-Type", "application/json");
+        if !self.gzip {
+            return builder.body(body);
+        }
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        match encoder.write_all(&body).and_then(|_| encoder.finish()) {
+            Ok(compressed) => builder.header("Content-Encoding", "gzip").body(compressed),
+            Err(_) => builder.body(body),
+        }
+    }
+
+    /// Sends a request once, logging its method, url, status and duration at debug level
+    /// with `log` and/or as a `tracing` event, depending on the enabled features
+    async fn execute(&self, request: reqwest::Request) -> reqwest::Result<reqwest::Response> {
+        #[cfg(any(feature = "logging", feature = "tracing"))]
+        let (method, url, started) = (
+            request.method().clone(),
+            request.url().clone(),
+            Instant::now(),
+        );
+
+        let result = self.client.execute(request).await;
+
+        #[cfg(any(feature = "logging", feature = "tracing"))]
+        {
+            let status = match &result {
+                Ok(response) => response.status().as_u16().to_string(),
+                Err(_) => "error".to_string(),
+            };
+            let elapsed_ms = started.elapsed().as_millis();
+            #[cfg(feature = "logging")]
+            debug!(target: "stdout", "[qdrant] method={} url={} status={} elapsed_ms={}", method, url, status, elapsed_ms);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(%method, %url, %status, elapsed_ms, "qdrant request");
+        }
+
+        result
+    }
+
+    /// Sends a request, retrying it on transport errors, server errors and rate limiting (429)
+    /// up to the configured number of retries.
+    /// Retries wait with an exponential backoff, or as long as the `Retry-After` header of a 429 says
+    /// but at most a minute, plus some jitter.
+    /// Requests with a body larger than the configured maximum fail without being sent.
+    async fn send(
+        &self,
+        builder: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, QdrantError> {
+        let mut request = builder.build()?;
+        if let Some(max_body_size) = self.max_body_size {
+            let size = request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map_or(0, |body| body.len());
+            if size > max_body_size {
+                return Err(QdrantError::InvalidInput(format!(
+                    "The request body of {} bytes exceeds the maximum of {} bytes. Send the points in smaller chunks, e.g. with `upsert_points_iter`.",
+                    size, max_body_size
+                )));
+            }
+        }
+        let mut attempt = 0;
+        loop {
+            // keep a copy of the request in case it has to be sent again
+            let next = match attempt < self.retries {
+                true => request.try_clone(),
+                false => None,
+            };
+
+            let result = self.execute(request).await;
+            let next = match next {
+                Some(next) => next,
+                None => return Ok(result?),
+            };
+
+            #[cfg_attr(
+                not(any(feature = "logging", feature = "tracing")),
+                allow(unused_variables)
+            )]
+            let (reason, retry_after) = match result {
+                Ok(response) if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => (
+                    format!("status {}", response.status()),
+                    retry_after(response.headers()),
+                ),
+                Ok(response) if !response.status().is_server_error() => return Ok(response),
+                Ok(response) => (format!("status {}", response.status()), None),
+                Err(e) => (e.to_string(), None),
+            };
+
+            attempt += 1;
+
+            #[cfg(feature = "logging")]
+            warn!(target: "stdout", "[qdrant] Request failed ({}), retry {}/{}", reason, attempt, self.retries);
+            #[cfg(feature = "tracing")]
+            tracing::warn!(%reason, attempt, retries = self.retries, "qdrant request failed, retrying");
+
+            let delay = retry_after.unwrap_or(Duration::from_millis(100 << attempt.min(10)));
+            sleep(delay + jitter(delay / 4)).await;
+            request = next;
+        }
+    }
+}
+
+/// Connects to `http://localhost:6333`, same as `Qdrant::new()`
+impl Default for Qdrant {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for the `Qdrant` client
+#[derive(Default)]
+pub struct QdrantBuilder {
+    url: Option<String>,
+    api_key: Option<String>,
+    bearer_token: Option<String>,
+    timeout: Option<Duration>,
+    retries: u32,
+    default_headers: HeaderMap,
+    root_certificates: Vec<Certificate>,
+    accept_invalid_certs: bool,
+    gzip: bool,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Option<Duration>>,
+    max_body_size: Option<usize>,
+    client: Option<reqwest::Client>,
+    api_key_env: Option<String>,
+}
+
+/// Redacts the api key and the bearer token, so that the builder can be logged
+impl std::fmt::Debug for QdrantBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let redacted = |secret: &Option<String>| secret.as_ref().map(|_| "<redacted>");
+        f.debug_struct("QdrantBuilder")
+            .field("url", &self.url)
+            .field("api_key", &redacted(&self.api_key))
+            .field("bearer_token", &redacted(&self.bearer_token))
+            .field("timeout", &self.timeout)
+            .field("retries", &self.retries)
+            .field("default_headers", &self.default_headers)
+            .field("root_certificates", &self.root_certificates.len())
+            .field("accept_invalid_certs", &self.accept_invalid_certs)
+            .field("gzip", &self.gzip)
+            .field("pool_max_idle_per_host", &self.pool_max_idle_per_host)
+            .field("pool_idle_timeout", &self.pool_idle_timeout)
+            .field("max_body_size", &self.max_body_size)
+            .field("client", &self.client)
+            .field("api_key_env", &self.api_key_env)
+            .finish()
+    }
+}
+
+impl QdrantBuilder {
+    pub fn new() -> QdrantBuilder {
+        QdrantBuilder::default()
+    }
+
+    /// Sets the url of the Qdrant service. Defaults to `http://localhost:6333`
+    ///
+    /// The url may contain a path prefix for gateway setups, e.g. `https://gw.internal/qdrant/`
+    /// sends requests to `https://gw.internal/qdrant/collections`. Trailing slashes are ignored.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the api key sent in the `api-key` header
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Reads the api key from the `QDRANT_API_KEY` environment variable when the client is built.
+    /// `build` fails if the variable is not set.
+    pub fn api_key_from_env(self) -> Self {
+        self.api_key_from_env_var("QDRANT_API_KEY")
+    }
+
+    /// Same as `api_key_from_env`, reading the given environment variable instead
+    pub fn api_key_from_env_var(mut self, name: impl Into<String>) -> Self {
+        self.api_key_env = Some(name.into());
+        self
+    }
+
+    /// Sets the token sent in the `Authorization: Bearer` header
+    pub fn bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// Sets the timeout of each request
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fails requests whose body is larger than `bytes` before sending them, instead of letting
+    /// Qdrant or a proxy reset the connection. Compressed bodies are measured after compression. Unlimited by default.
+    pub fn max_body_size(mut self, bytes: usize) -> Self {
+        self.max_body_size = Some(bytes);
+        self
+    }
+
+    /// Sets how many times a request is retried on transport errors, server errors and rate limiting
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the headers sent with every request
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Adds a trusted root certificate, e.g. the private CA of an on-prem Qdrant.
+    /// Use `Certificate::from_pem` to load one.
+    pub fn add_root_certificate(mut self, cert: Certificate) -> Self {
+        self.root_certificates.push(cert);
+        self
+    }
+
+    /// Accepts any TLS certificate, including self-signed and expired ones.
+    ///
+    /// This makes the connection vulnerable to man-in-the-middle attacks, so only use it in development.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// Compresses request bodies and accepts compressed responses with gzip. Defaults to `false`.
+    ///
+    /// Large upserts and search results shrink a lot, which helps on slow or metered links.
+    pub fn gzip(mut self, gzip: bool) -> Self {
+        self.gzip = gzip;
+        self
+    }
+
+    /// Sets the maximum number of idle connections kept open to the server, for highly concurrent callers.
+    /// Defaults to reqwest's default, which is unlimited.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Sets how long idle connections are kept open, `None` keeping them open forever.
+    /// Defaults to reqwest's default of 90 seconds.
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Uses an existing HTTP client, e.g. one shared with the rest of the application.
+    ///
+    /// The client is used as is: the `timeout`, `default_headers`, certificate, response
+    /// compression and connection pool settings of the builder are ignored and must be set on the client instead.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn build(mut self) -> Result<Qdrant, QdrantError> {
+        if let Some(name) = &self.api_key_env {
+            match std::env::var(name) {
+                Ok(api_key) => self.api_key = Some(api_key),
+                Err(e) => {
+                    return Err(QdrantError::InvalidInput(format!(
+                        "Failed to read the api key from the environment variable {}: {}",
+                        name, e
+                    )))
+                }
+            }
+        }
+        if let Some(api_key) = &self.api_key {
+            if HeaderValue::from_str(api_key).is_err() {
+                return Err(QdrantError::InvalidInput(
+                    "The api key is not a valid header value".to_string(),
+                ));
+            }
+        }
+        if let Some(token) = &self.bearer_token {
+            if HeaderValue::from_str(&format!("Bearer {}", token)).is_err() {
+                return Err(QdrantError::InvalidInput(
+                    "The bearer token is not a valid header value".to_string(),
+                ));
+            }
+        }
+
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut client_builder = reqwest::Client::builder()
+                    .default_headers(self.default_headers)
+                    .gzip(self.gzip);
+                if let Some(timeout) = self.timeout {
+                    client_builder = client_builder.timeout(timeout);
+                }
+                for cert in self.root_certificates {
+                    client_builder = client_builder.add_root_certificate(cert);
+                }
+                if self.accept_invalid_certs {
+                    client_builder = client_builder.danger_accept_invalid_certs(true);
+                }
+                if let Some(max) = self.pool_max_idle_per_host {
+                    client_builder = client_builder.pool_max_idle_per_host(max);
+                }
+                if let Some(timeout) = self.pool_idle_timeout {
+                    client_builder = client_builder.pool_idle_timeout(timeout);
+                }
+                client_builder.build()?
+            }
+        };
+
+        Ok(Qdrant {
+            url_base: self
+                .url
+                .as_deref()
+                .unwrap_or("http://localhost:6333")
+                .trim_end_matches('/')
+                .to_string(),
+            api_key: self.api_key,
+            bearer_token: self.bearer_token,
+            retries: self.retries,
+            gzip: self.gzip,
+            max_body_size: self.max_body_size,
+            client,
+            info_cache: Mutex::new(HashMap::new()),
+        })
+    }
+}
+
+/// Shortcut functions
+impl Qdrant {
+    /// Shortcut functions
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn collection_info(&self, collection_name: &str) -> Result<u64, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "get collection info: '{}'", collection_name);
+
+        let response: QdrantResponse<PointsCount> =
+            self.collection_info_request(collection_name).await?;
+        Ok(response.result.points_count)
+    }
+
+    /// Same as `collection_info`, but reuses the point count fetched for the collection less than `ttl` ago,
+    /// e.g. when many tasks poll the size of the same collection. Concurrent calls may all fetch an expired count.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn collection_info_cached(
+        &self,
+        collection_name: &str,
+        ttl: Duration,
+    ) -> Result<u64, QdrantError> {
+        let cached = self
+            .info_cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(collection_name)
+            .copied();
+        if let Some((fetched, points_count)) = cached {
+            if fetched.elapsed() < ttl {
+                return Ok(points_count);
+            }
+        }
+
+        let points_count = self.collection_info(collection_name).await?;
+        self.info_cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(collection_name.to_string(), (Instant::now(), points_count));
+        Ok(points_count)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn collection_info_full(
+        &self,
+        collection_name: &str,
+    ) -> Result<CollectionInfo, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "get full collection info: '{}'", collection_name);
+
+        let response: QdrantResponse<CollectionInfo> =
+            self.collection_info_request(collection_name).await?;
+        Ok(response.result)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn create_collection(
+        &self,
+        collection_name: &str,
+        size: u32,
+    ) -> Result<(), QdrantError> {
+        self.create_collection_with_options(
+            collection_name,
+            CreateCollectionOptions::new(size as u64),
+        )
+        .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn create_collection_with_options(
+        &self,
+        collection_name: &str,
+        options: CreateCollectionOptions,
+    ) -> Result<(), QdrantError> {
+        options.validate()?;
+        let params = serde_json::to_value(&options)?;
+        self.create_collection_raw(collection_name, params).await
+    }
+
+    /// Creates a collection from the complete JSON body of the Qdrant create collection request,
+    /// for settings that `CreateCollectionOptions` does not cover.
+    /// Fails with `CollectionExists` if the collection already exists, like `create_collection`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn create_collection_raw(
+        &self,
+        collection_name: &str,
+        params: Value,
+    ) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "create collection '{}'", collection_name);
+
+        match self.collection_exists(collection_name).await {
+            Ok(false) => (),
+            Ok(true) => {
+                let err = QdrantError::CollectionExists(collection_name.to_string());
+
+                #[cfg(feature = "logging")]
+                error!(target: "stdout", "{}", &err);
+
+                return Err(err);
+            }
+            Err(e) => {
+                #[cfg(feature = "logging")]
+                error!(target: "stdout", "{}", e);
+
+                return Err(e);
+            }
+        }
+
+        if !self.create_collection_api(collection_name, &params).await? {
+            return Err(QdrantError::Unexpected(format!(
+                "Failed to create collection '{}'",
+                collection_name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Gets the size and the distance of the unnamed vector space of the collection,
+    /// e.g. to check the dimension of embeddings before upserting them.
+    /// Fails with `InvalidInput` if the collection has named vector spaces instead.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn vector_params(
+        &self,
+        collection_name: &str,
+    ) -> Result<(u64, Distance), QdrantError> {
+        let info = self.collection_info_full(collection_name).await?;
+        match info.config.params.vectors {
+            VectorsConfig::Single(params) => Ok((params.size, params.distance)),
+            VectorsConfig::Multi(spaces) => {
+                let mut names: Vec<String> = spaces.into_keys().collect();
+                names.sort();
+                Err(QdrantError::InvalidInput(format!(
+                    "Collection '{}' has named vector spaces: {}",
+                    collection_name,
+                    names.join(", ")
+                )))
+            }
+        }
+    }
+
+    /// Polls the status of the collection every `poll_interval` until it is `Green`, i.e. all its points are indexed,
+    /// and fails if it is still not after `timeout`
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn wait_for_indexing(
+        &self,
+        collection_name: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<(), QdrantError> {
+        let started = Instant::now();
+        loop {
+            let info = self.collection_info_full(collection_name).await?;
+            if info.status == CollectionStatus::Green {
+                return Ok(());
+            }
+            if started.elapsed() >= timeout {
+                return Err(QdrantError::Unexpected(format!(
+                    "Collection '{}' is still {:?} after {:?}",
+                    collection_name, info.status, timeout
+                )));
+            }
+            sleep(poll_interval).await;
+        }
+    }
+
+    /// Deletes the collection if it exists, then creates it with the given options.
+    /// Safe to call whether or not the collection exists, e.g. in test setup or before reindexing.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn recreate_collection(
+        &self,
+        collection_name: &str,
+        options: CreateCollectionOptions,
+    ) -> Result<(), QdrantError> {
+        self.delete_collection_if_exists(collection_name).await?;
+        self.create_collection_with_options(collection_name, options)
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn update_collection(
+        &self,
+        collection_name: &str,
+        options: UpdateCollectionOptions,
+    ) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "update collection '{}'", collection_name);
+
+        let params = serde_json::to_value(&options)?;
+        if !self.update_collection_api(collection_name, &params).await? {
+            return Err(QdrantError::Unexpected(format!(
+                "Failed to update collection '{}'",
+                collection_name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Creates a shard for the key in a collection using custom sharding
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn create_shard_key(
+        &self,
+        collection_name: &str,
+        shard_key: ShardKey,
+    ) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "create shard key {:?} in collection '{}'", shard_key, collection_name);
+
+        let params = json!({
+            "shard_key": shard_key,
+        });
+        if !self.create_shard_key_api(collection_name, &params).await? {
+            return Err(QdrantError::Unexpected(format!(
+                "Failed to create shard key {:?} in collection '{}'",
+                shard_key, collection_name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Deletes the shards of the key, and all their points
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn delete_shard_key(
+        &self,
+        collection_name: &str,
+        shard_key: ShardKey,
+    ) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "delete shard key {:?} from collection '{}'", shard_key, collection_name);
+
+        let params = json!({
+            "shard_key": shard_key,
+        });
+        if !self.delete_shard_key_api(collection_name, &params).await? {
+            return Err(QdrantError::Unexpected(format!(
+                "Failed to delete shard key {:?} from collection '{}'",
+                shard_key, collection_name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Gets the peers of the cluster and the state of the consensus
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn cluster_info(&self) -> Result<ClusterInfo, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "get cluster info");
+
+        let response: QdrantResponse<ClusterInfo> = self.cluster_info_request().await?;
+        Ok(response.result)
+    }
+
+    /// Gets the shards of a collection, where they are stored and their state
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn collection_cluster_info(
+        &self,
+        collection_name: &str,
+    ) -> Result<CollectionClusterInfo, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "get cluster info of collection '{}'", collection_name);
+
+        let response: QdrantResponse<CollectionClusterInfo> = self
+            .collection_cluster_info_request(collection_name)
+            .await?;
+        Ok(response.result)
+    }
+
+    /// Checks that the server is up and returns the round-trip time of the health check.
+    /// The request is sent once, without retries, so the latency is not skewed by backoffs.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn ping(&self) -> Result<Duration, QdrantError> {
+        let url = format!("{}/healthz", self.url_base);
+        let request = self.request(Method::GET, &url).build()?;
+
+        let started = Instant::now();
+        let res = self.execute(request).await?;
+        let elapsed = started.elapsed();
+
+        if !res.status().is_success() {
+            return Err(response_error(res, "ping").await);
+        }
+        Ok(elapsed)
+    }
+
+    /// Gets the metrics of the server in the Prometheus text format
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn metrics(&self) -> Result<String, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "get metrics");
+
+        self.metrics_api().await
+    }
+
+    /// Gets the telemetry data of the server
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn telemetry(&self) -> Result<Value, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "get telemetry");
+
+        let response: QdrantResponse<Value> = self.telemetry_request().await?;
+        Ok(response.result)
+    }
+
+    /// Creates a snapshot of the whole storage of the node, all collections included, and waits until it is written
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn create_full_snapshot(&self) -> Result<SnapshotDescription, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "create full snapshot");
+
+        let v = self.create_full_snapshot_api().await?;
+        take_result(v)
+    }
+
+    /// Lists the snapshots of the whole storage
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn list_full_snapshots(&self) -> Result<Vec<SnapshotDescription>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "list full snapshots");
+
+        let v = self.list_full_snapshots_api().await?;
+        take_result(v)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn delete_full_snapshot(&self, snapshot_name: &str) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "delete full snapshot '{}'", snapshot_name);
+
+        self.delete_full_snapshot_api(snapshot_name).await
+    }
+
+    /// Downloads a snapshot of the whole storage into `writer`, chunk by chunk, and returns its size in bytes
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn download_full_snapshot(
+        &self,
+        snapshot_name: &str,
+        writer: &mut impl Write,
+    ) -> Result<u64, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "download full snapshot '{}'", snapshot_name);
+
+        self.download_full_snapshot_api(snapshot_name, writer).await
+    }
+
+    /// Recovers a collection from a snapshot, creating the collection if it does not exist
+    /// and replacing its data if it does. Waits until the recovery is done.
+    ///
+    /// `source` is either a url Qdrant downloads the snapshot from, or the path of a local file,
+    /// which is read into memory and uploaded; see `SnapshotSource`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn recover_collection_from_snapshot(
+        &self,
+        collection_name: &str,
+        source: impl Into<SnapshotSource>,
+    ) -> Result<(), QdrantError> {
+        let source = source.into();
+
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "recover collection '{}' from snapshot {:?}", collection_name, source);
+
+        match source {
+            SnapshotSource::Location(location) => {
+                self.recover_collection_from_snapshot_api(collection_name, &location)
+                    .await
+            }
+            SnapshotSource::File(path) => {
+                let content = std::fs::read(&path).map_err(|e| {
+                    QdrantError::InvalidInput(format!(
+                        "Failed to read snapshot file '{}': {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+                let file_name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "snapshot".to_string());
+                self.upload_collection_snapshot_api(collection_name, &file_name, content)
+                    .await
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn list_collections(&self) -> Result<Vec<String>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "list collections");
+
+        self.list_collections_api().await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn collection_exists(&self, collection_name: &str) -> Result<bool, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "check collection existence: {}", collection_name);
+
+        self.collection_exists_api(collection_name).await
+    }
+
+    /// Deletes the collection, succeeding whether or not it exists, e.g. in teardown code
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn delete_collection_if_exists(
+        &self,
+        collection_name: &str,
+    ) -> Result<(), QdrantError> {
+        match self.delete_collection(collection_name).await {
+            Ok(()) | Err(QdrantError::CollectionNotFound(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn delete_collection(&self, collection_name: &str) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "delete collection '{}'", collection_name);
+
+        match self.collection_exists(collection_name).await {
+            Ok(true) => (),
+            Ok(false) => {
+                let err = QdrantError::CollectionNotFound(collection_name.to_string());
+
+                #[cfg(feature = "logging")]
+                error!(target: "stdout", "{}", &err);
+
+                return Err(err);
+            }
+            Err(e) => {
+                #[cfg(feature = "logging")]
+                error!(target: "stdout", "{}", e);
+
+                return Err(e);
+            }
+        }
+
+        if !self.delete_collection_api(collection_name).await? {
+            return Err(QdrantError::Unexpected(format!(
+                "Failed to delete collection '{}'",
+                collection_name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Inserts the points, or replaces the stored points with the same ids.
+    ///
+    /// Every point carries its id, so an upsert is idempotent: replaying it, e.g. after a timeout,
+    /// leaves the collection as a single upsert would, without duplicating points.
+    /// This is what makes it safe for the client to retry upserts when `retries` is set.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn upsert_points<P: Serialize>(
+        &self,
+        collection_name: &str,
+        points: Vec<Point<P>>,
+    ) -> Result<(), QdrantError> {
+        self.upsert_points_with_options(collection_name, points, &WriteOptions::default())
+            .await
+    }
+
+    /// Same as `upsert_points`, with the given write options
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn upsert_points_with_options<P: Serialize>(
+        &self,
+        collection_name: &str,
+        points: Vec<Point<P>>,
+        options: &WriteOptions,
+    ) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "upsert {} points to collection '{}'", points.len(), collection_name);
+
+        for point in points.iter() {
+            point.id.validate()?;
+        }
+        if options.check_vector_sizes {
+            let info = self.collection_info_full(collection_name).await?;
+            for point in points.iter() {
+                check_vector_sizes(collection_name, &info.config.params.vectors, point)?;
+            }
+        }
+
+        let mut params = json!({
+            "points": points,
+        });
+        options.add_to(&mut params);
+        self.upsert_points_api_with_query(collection_name, &params, &options.query())
+            .await
+    }
+
+    /// Upserts the points of an iterator in chunks of `chunk_size` points, so they never need to be all in memory.
+    /// A failed chunk does not stop the following ones; the failures are reported in the summary.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn upsert_points_iter<P, I>(
+        &self,
+        collection_name: &str,
+        points: I,
+        chunk_size: usize,
+    ) -> UpsertSummary
+    where
+        P: Serialize,
+        I: IntoIterator<Item = Point<P>>,
+    {
+        let chunk_size = chunk_size.max(1);
+        let mut points = points.into_iter();
+        let mut summary = UpsertSummary::default();
+        let mut position = 0;
+        loop {
+            let chunk: Vec<Point<P>> = points.by_ref().take(chunk_size).collect();
+            if chunk.is_empty() {
+                return summary;
+            }
+
+            let len = chunk.len() as u64;
+            match self.upsert_points(collection_name, chunk).await {
+                Ok(()) => summary.upserted += len,
+                Err(e) => summary.errors.push((position, e)),
+            }
+            position += len;
+        }
+    }
+
+    /// Upserts the points in chunks of `chunk_size` points, with at most `max_in_flight` chunks sent concurrently,
+    /// so ingestion is parallel without overwhelming the server.
+    /// Stops at the first failed chunk and returns its error; chunks already written stay written.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn upsert_points_concurrent<P: Serialize>(
+        &self,
+        collection_name: &str,
+        points: Vec<Point<P>>,
+        chunk_size: usize,
+        max_in_flight: usize,
+    ) -> Result<(), QdrantError> {
+        let chunk_size = chunk_size.max(1);
+        let mut points = points.into_iter();
+        let chunks = std::iter::from_fn(move || {
+            let chunk: Vec<Point<P>> = points.by_ref().take(chunk_size).collect();
+            (!chunk.is_empty()).then_some(chunk)
+        });
+
+        stream::iter(chunks.map(Ok))
+            .try_for_each_concurrent(max_in_flight.max(1), |chunk| {
+                self.upsert_points(collection_name, chunk)
+            })
+            .await
+    }
+
+    /// Searches the `limit` points nearest to the vector.
+    /// Without a `score_threshold`, no point is filtered out by its score.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn search_points<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        vector: Vec<f32>,
+        limit: u64,
+        score_threshold: Option<f32>,
+    ) -> Result<Vec<ScoredPoint<P>>, QdrantError> {
+        let options = SearchOptions {
+            score_threshold,
+            ..Default::default()
+        };
+        self.search_points_with_options(collection_name, vector, limit, &options)
+            .await
+    }
+
+    /// Searches the points nearest to the vector, with the given options
+    pub async fn search_points_with_options<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        vector: Vec<f32>,
+        limit: u64,
+        options: &SearchOptions,
+    ) -> Result<Vec<ScoredPoint<P>>, QdrantError> {
+        let response = self
+            .search_points_response(collection_name, vector, limit, options)
+            .await?;
+        Ok(response.result)
+    }
+
+    /// Same as `search_points_with_options`, but also returns the status and the search time reported by Qdrant
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name, count = tracing::field::Empty)))]
+    pub async fn search_points_response<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        vector: Vec<f32>,
+        limit: u64,
+        options: &SearchOptions,
+    ) -> Result<QdrantResponse<Vec<ScoredPoint<P>>>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "search points in collection '{}'", collection_name);
+
+        let params = options.request_body(vector, limit);
+        let query = options.query();
+        let response: QdrantResponse<Vec<ScoredPoint<P>>> = self
+            .search_points_request(collection_name, &params, &query)
+            .await?;
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("count", response.result.len());
+        Ok(response)
+    }
+
+    /// Same as `search_points_with_options`, but returns the whole JSON response, including
+    /// the fields the typed results do not capture
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn search_points_raw(
+        &self,
+        collection_name: &str,
+        vector: Vec<f32>,
+        limit: u64,
+        options: &SearchOptions,
+    ) -> Result<Value, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "search points in collection '{}'", collection_name);
+
+        let params = options.request_body(vector, limit);
+        let query = options.query();
+        self.search_points_api_with_query(collection_name, &params, &query)
+            .await
+    }
+
+    /// Streams the points nearest to the vector, best first, fetching `page_size` points at a time as the stream is polled.
+    /// The stream ends after a page with fewer than `page_size` points, or after the first error.
+    pub fn search_stream<'a, P: DeserializeOwned + 'a>(
+        &'a self,
+        collection_name: &'a str,
+        vector: Vec<f32>,
+        page_size: u64,
+        filter: Option<Value>,
+    ) -> impl Stream<Item = Result<ScoredPoint<P>, QdrantError>> + 'a {
+        let page_size = page_size.max(1);
+        let options = SearchOptions {
+            filter,
+            offset: Some(0),
+            ..Default::default()
+        };
+
+        // the state is the options of the next page, `None` once the last page is fetched
+        stream::unfold(Some(options), move |options| {
+            let vector = vector.clone();
+            async move {
+                let mut options = options?;
+                let result = self
+                    .search_points_with_options::<P>(collection_name, vector, page_size, &options)
+                    .await;
+                match result {
+                    Ok(points) => {
+                        let next = match (points.len() as u64) < page_size {
+                            true => None,
+                            false => {
+                                options.offset = Some(options.offset.unwrap_or(0) + page_size);
+                                Some(options)
+                            }
+                        };
+                        let points: Vec<Result<ScoredPoint<P>, QdrantError>> =
+                            points.into_iter().map(Ok).collect();
+                        Some((stream::iter(points), next))
+                    }
+                    Err(e) => Some((stream::iter(vec![Err(e)]), None)),
+                }
+            }
+        })
+        .flatten()
+    }
+
+    /// Searches the same vector in several collections concurrently, and merges the results
+    /// into the `limit` best points overall, best first.
+    ///
+    /// Scores are compared as they are, so the collections should use the same distance,
+    /// one where higher scores are better such as Cosine or Dot.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn search_multi<P: DeserializeOwned>(
+        &self,
+        collections: &[&str],
+        vector: Vec<f32>,
+        limit: u64,
+        score_threshold: Option<f32>,
+    ) -> Result<Vec<MultiSearchHit<P>>, QdrantError> {
+        let searches = collections.iter().map(|collection_name| {
+            self.search_points::<P>(collection_name, vector.clone(), limit, score_threshold)
+        });
+        let results = futures::future::join_all(searches).await;
+
+        let mut hits = Vec::new();
+        for (collection_name, result) in collections.iter().zip(results) {
+            hits.extend(result?.into_iter().map(|point| MultiSearchHit {
+                collection: collection_name.to_string(),
+                point,
+            }));
+        }
+        hits.sort_by(|a, b| b.point.score.total_cmp(&a.point.score));
+        hits.truncate(limit as usize);
+        Ok(hits)
+    }
+
+    /// Samples `sample` points, optionally among the points matching the filter, and returns
+    /// the `limit` nearest other sampled points of each of them, as pairs with their scores.
+    /// Useful for clustering and near-duplicate detection.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn search_matrix(
+        &self,
+        collection_name: &str,
+        sample: u64,
+        limit: u64,
+        filter: Option<Value>,
+    ) -> Result<Vec<MatrixPair>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "search distance matrix of {} points in collection '{}'", sample, collection_name);
+
+        let mut params = json!({
+            "sample": sample,
+            "limit": limit,
+        });
+        if let Some(filter) = filter {
+            params["filter"] = filter;
+        }
+
+        let response: QdrantResponse<MatrixPairsResult> =
+            self.search_matrix_request(collection_name, &params).await?;
+        Ok(response.result.pairs)
+    }
+
+    /// Searches the points nearest to the vector, grouped by the value of the `group_by` payload field.
+    /// Returns at most `limit` groups of at most `group_size` points each, e.g. the best chunks of the best documents.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn search_groups<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        vector: Vec<f32>,
+        group_by: &str,
+        group_size: u64,
+        limit: u64,
+        filter: Option<Value>,
+    ) -> Result<Vec<PointGroup<P>>, QdrantError> {
+        self.search_groups_with_lookup(
+            collection_name,
+            vector,
+            group_by,
+            group_size,
+            limit,
+            filter,
+            None,
+        )
+        .await
+    }
+
+    /// Same as `search_groups`, also looking up a point of another collection for each group, see `PointGroup::lookup`
+    #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn search_groups_with_lookup<P: DeserializeOwned, L: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        vector: Vec<f32>,
+        group_by: &str,
+        group_size: u64,
+        limit: u64,
+        filter: Option<Value>,
+        with_lookup: Option<WithLookup>,
+    ) -> Result<Vec<PointGroup<P, L>>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "search point groups by '{}' in collection '{}'", group_by, collection_name);
+
+        let mut params = json!({
+            "vector": vector,
+            "group_by": group_by,
+            "group_size": group_size,
+            "limit": limit,
+            "with_payload": true,
+            "with_vector": true,
+        });
+        if let Some(filter) = filter {
+            params["filter"] = filter;
+        }
+        if let Some(with_lookup) = with_lookup {
+            params["with_lookup"] = json!(with_lookup);
+        }
+
+        let response: QdrantResponse<GroupsResult<P, L>> =
+            self.search_groups_request(collection_name, &params).await?;
+        Ok(response.result.groups)
+    }
+
+    /// Searches the points nearest to the vector, keeping at most one point, the best one, per value of
+    /// the `group_by` payload field, e.g. one chunk per document. Returns up to `limit` points, best first.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn search_points_dedup<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        vector: Vec<f32>,
+        limit: u64,
+        group_by: &str,
+    ) -> Result<Vec<ScoredPoint<P>>, QdrantError> {
+        let groups: Vec<PointGroup<P>> = self
+            .search_groups(collection_name, vector, group_by, 1, limit, None)
+            .await?;
+        Ok(groups
+            .into_iter()
+            .filter_map(|group| group.hits.into_iter().next())
+            .collect())
+    }
+
+    /// Searches the points near the target that lie on the positive side of every context pair.
+    /// Each pair is a `(positive, negative)` example, so results can be steered without a clean query vector.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn discover_points<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        target: VectorInput,
+        context: Vec<(PointId, PointId)>,
+        limit: u64,
+        filter: Option<Value>,
+    ) -> Result<Vec<ScoredPoint<P>>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "discover points with {} context pairs in collection '{}'", context.len(), collection_name);
+
+        let context: Vec<Value> = context
+            .iter()
+            .map(|(positive, negative)| json!({ "positive": positive, "negative": negative }))
+            .collect();
+        let mut params = json!({
+            "target": target,
+            "context": context,
+            "limit": limit,
+            "with_payload": true,
+            "with_vector": true,
+        });
+        if let Some(filter) = filter {
+            params["filter"] = filter;
+        }
+
+        let v = self.discover_points_api(collection_name, &params).await?;
+        take_result(v)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn query_points<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        request: QueryRequest,
+    ) -> Result<Vec<ScoredPoint<P>>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "query points in collection '{}'", collection_name);
+
+        let params = serde_json::to_value(&request)?;
+        let response: QdrantResponse<PointsResult<P>> =
+            self.query_points_request(collection_name, &params).await?;
+        Ok(response.result.points)
+    }
+
+    /// Recommends the points most similar to the positive examples and least similar to the negative ones
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn recommend_points<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        request: RecommendRequest,
+    ) -> Result<Vec<ScoredPoint<P>>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "recommend points in collection '{}'", collection_name);
+
+        let params = serde_json::to_value(&request)?;
+        let v = self.recommend_points_api(collection_name, &params).await?;
+        take_result(v)
+    }
+
+    /// Recommends points from example vectors, e.g. embeddings of items that are not stored in the collection
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn recommend_by_vectors<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        positive: Vec<Vec<f32>>,
+        negative: Vec<Vec<f32>>,
+        limit: u64,
+        filter: Option<Value>,
+    ) -> Result<Vec<ScoredPoint<P>>, QdrantError> {
+        let request = RecommendRequest {
+            positive: positive.into_iter().map(VectorInput::from).collect(),
+            negative: negative.into_iter().map(VectorInput::from).collect(),
+            filter,
+            limit,
+            ..Default::default()
+        };
+        self.recommend_points(collection_name, request).await
+    }
+
+    /// Runs several recommend requests in a single round trip.
+    /// The results are in the order of the requests.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn recommend_batch<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        requests: Vec<RecommendRequest>,
+    ) -> Result<Vec<Vec<ScoredPoint<P>>>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "recommend points with {} requests in collection '{}'", requests.len(), collection_name);
+
+        let params = json!({
+            "searches": requests,
+        });
+        let v = self.recommend_batch_api(collection_name, &params).await?;
+        take_result(v)
+    }
+
+    /// Recommends points grouped by the value of the `group_by` payload field.
+    /// Returns at most `request.limit` groups of at most `group_size` points each.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn recommend_groups<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        request: RecommendRequest,
+        group_by: &str,
+        group_size: u64,
+    ) -> Result<Vec<PointGroup<P>>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "recommend point groups by '{}' in collection '{}'", group_by, collection_name);
+
+        let mut params = serde_json::to_value(&request)?;
+        params["group_by"] = json!(group_by);
+        params["group_size"] = json!(group_size);
+
+        let response: QdrantResponse<GroupsResult<P>> = self
+            .recommend_groups_request(collection_name, &params)
+            .await?;
+        Ok(response.result.groups)
+    }
+
+    /// Runs the prefetches and merges their results into a single ranking with the given fusion method.
+    /// This is the usual way to combine dense and sparse retrieval.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn hybrid_search<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        prefetches: Vec<Prefetch>,
+        fusion: Fusion,
+        limit: u64,
+    ) -> Result<Vec<ScoredPoint<P>>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "hybrid search with {} prefetches in collection '{}'", prefetches.len(), collection_name);
+
+        let request = QueryRequest {
+            prefetch: prefetches,
+            query: Some(Query::Fusion(fusion)),
+            limit: Some(limit),
+            ..Default::default()
+        };
+        self.query_points(collection_name, request).await
+    }
+
+    /// Searches the points nearest to a sparse vector in the named sparse vector space
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn search_sparse_points<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        vector_name: &str,
+        vector: SparseVector,
+        limit: u64,
+    ) -> Result<Vec<ScoredPoint<P>>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "search points by sparse vector '{}' in collection '{}'", vector_name, collection_name);
+
+        let request = QueryRequest {
+            query: Some(Query::Nearest(VectorInput::Sparse(vector))),
+            using: Some(vector_name.to_string()),
+            limit: Some(limit),
+            ..Default::default()
+        };
+        self.query_points(collection_name, request).await
+    }
+
+    /// Gets points by their ids, e.g. `get_points("my_test", vec![1, 2, 3])` or `get_points("my_test", &ids)`
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn get_points<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        ids: impl IntoIterator<Item = impl Into<PointId>>,
+    ) -> Result<Vec<Point<P>>, QdrantError> {
+        let ids: Vec<PointId> = ids.into_iter().map(Into::into).collect();
+        self.get_points_with_options(collection_name, &ids, &GetPointsOptions::default())
+            .await
+    }
+
+    /// Gets points aligned with the given ids: the n-th item is the point with the n-th id,
+    /// or `None` if it does not exist. Duplicate ids are requested only once.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn get_points_ordered<P: DeserializeOwned + Clone>(
+        &self,
+        collection_name: &str,
+        ids: impl IntoIterator<Item = impl Into<PointId>>,
+    ) -> Result<Vec<Option<Point<P>>>, QdrantError> {
+        let ids: Vec<PointId> = ids.into_iter().map(|id| id.into().normalized()).collect();
+        let mut seen = HashSet::new();
+        let unique: Vec<PointId> = ids.iter().filter(|id| seen.insert(*id)).cloned().collect();
+
+        let points: Vec<Point<P>> = self
+            .get_points_with_options(collection_name, &unique, &GetPointsOptions::default())
+            .await?;
+        let by_id: HashMap<PointId, Point<P>> = points
+            .into_iter()
+            .map(|point| (point.id.normalized(), point))
+            .collect();
+        Ok(ids.iter().map(|id| by_id.get(id).cloned()).collect())
+    }
+
+    /// Gets points, choosing whether their vectors and which of their payload are returned
+    pub async fn get_points_with_options<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        ids: &[PointId],
+        options: &GetPointsOptions,
+    ) -> Result<Vec<Point<P>>, QdrantError> {
+        let response = self
+            .get_points_response(collection_name, ids, options)
+            .await?;
+        Ok(response.result)
+    }
+
+    /// Same as `get_points_with_options`, but also returns the status and the time reported by Qdrant
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name, count = tracing::field::Empty)))]
+    pub async fn get_points_response<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        ids: &[PointId],
+        options: &GetPointsOptions,
+    ) -> Result<QdrantResponse<Vec<Point<P>>>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "get points from collection '{}'", collection_name);
+
+        let params = get_points_params(ids, options);
+        let query = consistency_query(options.consistency);
+        let response: QdrantResponse<Vec<Point<P>>> = self
+            .get_points_request(collection_name, &params, &query)
+            .await?;
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("count", response.result.len());
+        Ok(response)
+    }
+
+    /// Same as `get_points_with_options`, but returns the whole JSON response, including
+    /// the fields the typed points do not capture
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn get_points_raw(
+        &self,
+        collection_name: &str,
+        ids: &[PointId],
+        options: &GetPointsOptions,
+    ) -> Result<Value, QdrantError> {
+        #[cfg(feature = "logging")]
         info!(target: "stdout", "get points from collection '{}'", collection_name);
 
-        let params = json!({
-            "ids": ids,
-            "with_payload": true,
-            "with_vector": true,
-        });
+        let params = get_points_params(ids, options);
+        let query = consistency_query(options.consistency);
+        self.get_points_api_with_query(collection_name, &params, &query)
+            .await
+    }
+
+    /// Checks which of the given points exist, without fetching their vectors or payload.
+    /// The returned flags are in the order of `ids`, e.g. `points_exist("my_test", vec![1, 2, 3])`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn points_exist(
+        &self,
+        collection_name: &str,
+        ids: impl IntoIterator<Item = impl Into<PointId>>,
+    ) -> Result<Vec<bool>, QdrantError> {
+        let ids: Vec<PointId> = ids.into_iter().map(Into::into).collect();
+        let options = GetPointsOptions {
+            with_payload: PayloadSelector::None,
+            with_vector: false,
+            ..Default::default()
+        };
+        let points: Vec<Point> = self
+            .get_points_with_options(collection_name, &ids, &options)
+            .await?;
+
+        let found: HashSet<PointId> = points.into_iter().map(|p| p.id.normalized()).collect();
+        Ok(ids
+            .iter()
+            .map(|id| found.contains(&id.normalized()))
+            .collect())
+    }
+
+    /// Gets a point by its id, e.g. `get_point("my_test", 2)` or `get_point("my_test", &id)`
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn get_point<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        id: impl Into<PointId>,
+    ) -> Result<Point<P>, QdrantError> {
+        let id = id.into();
+
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "get point from collection '{}' with id {}", collection_name, id);
+
+        let v = self.get_point_api(collection_name, &id).await?;
+        take_result(v)
+    }
+
+    /// Gets a point, returning `None` if no point has the given id.
+    /// Unlike `get_point`, a missing point is not an error, while a missing collection still is.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn try_get_point<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        id: impl Into<PointId>,
+    ) -> Result<Option<Point<P>>, QdrantError> {
+        let points = self.get_points(collection_name, [id.into()]).await?;
+        Ok(points.into_iter().next())
+    }
+
+    /// Gets a page of points, in the order of their ids, or of a payload field with `order_by`.
+    /// Pass the returned `next_page_offset` as the `offset` of the next call to get the following page.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name, count = tracing::field::Empty)))]
+    pub async fn scroll_points<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        options: &ScrollOptions,
+    ) -> Result<ScrollResult<P>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "scroll points in collection '{}'", collection_name);
+
+        let params = options.request_body();
+        let query = consistency_query(options.consistency);
+        let response: QdrantResponse<ScrollResult<P>> = self
+            .scroll_points_request(collection_name, &params, &query)
+            .await?;
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("count", response.result.points.len());
+        Ok(response.result)
+    }
+
+    /// Retrieves points by id, by filter, or both.
+    ///
+    /// Qdrant's retrieve endpoint only takes ids, so requests with only ids are sent there and return a single page,
+    /// while requests with a filter, a limit or an offset are sent as scrolls, with the ids added to the filter.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn retrieve_points<P: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        request: &RetrieveRequest,
+    ) -> Result<ScrollResult<P>, QdrantError> {
+        let paged = request.filter.is_some() || request.limit.is_some() || request.offset.is_some();
+        if !paged && !request.ids.is_empty() {
+            let options = GetPointsOptions {
+                with_payload: request.with_payload.clone(),
+                with_vector: request.with_vector,
+                consistency: request.consistency,
+            };
+            let points = self
+                .get_points_with_options(collection_name, &request.ids, &options)
+                .await?;
+            return Ok(ScrollResult {
+                points,
+                next_page_offset: None,
+            });
+        }
+
+        let filter = match (request.ids.is_empty(), &request.filter) {
+            (true, filter) => filter.clone(),
+            (false, None) => Some(json!({ "must": [{ "has_id": request.ids }] })),
+            (false, Some(filter)) => Some(json!({ "must": [{ "has_id": request.ids }, filter] })),
+        };
+        let options = ScrollOptions {
+            filter,
+            limit: request.limit,
+            offset: request.offset.clone(),
+            with_payload: request.with_payload.clone(),
+            with_vector: request.with_vector,
+            order_by: None,
+            consistency: request.consistency,
+        };
+        self.scroll_points(collection_name, &options).await
+    }
+
+    /// Streams all the points matching the filter, fetching `page_size` points, at least 1, at a time as the stream is polled.
+    /// The stream ends after the last page or after the first error.
+    pub fn scroll_stream<'a, P: DeserializeOwned + 'a>(
+        &'a self,
+        collection_name: &'a str,
+        filter: Option<Value>,
+        page_size: u64,
+    ) -> impl Stream<Item = Result<Point<P>, QdrantError>> + 'a {
+        let options = ScrollOptions {
+            filter,
+            limit: Some(page_size.max(1)),
+            ..Default::default()
+        };
+
+        // the state is the options of the next page, `None` once the last page is fetched
+        stream::unfold(Some(options), move |options| async move {
+            let mut options = options?;
+            match self.scroll_points::<P>(collection_name, &options).await {
+                Ok(page) => {
+                    let next = page.next_page_offset.map(|offset| {
+                        options.offset = Some(offset);
+                        options
+                    });
+                    let points: Vec<Result<Point<P>, QdrantError>> =
+                        page.points.into_iter().map(Ok).collect();
+                    Some((stream::iter(points), next))
+                }
+                Err(e) => Some((stream::iter(vec![Err(e)]), None)),
+            }
+        })
+        .flatten()
+    }
+
+    /// Copies all the points of a collection into another one, `batch_size` points, at least 1, at a time.
+    /// Returns the number of points copied.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn copy_points(
+        &self,
+        src_collection: &str,
+        dst_collection: &str,
+        batch_size: u64,
+    ) -> Result<u64, QdrantError> {
+        self.copy_points_with(src_collection, dst_collection, batch_size, |_| {})
+            .await
+    }
+
+    /// Same as `copy_points`, but applies `transform` to every point before it is written to the destination
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn copy_points_with<F>(
+        &self,
+        src_collection: &str,
+        dst_collection: &str,
+        batch_size: u64,
+        mut transform: F,
+    ) -> Result<u64, QdrantError>
+    where
+        F: FnMut(&mut Point),
+    {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "copy points from collection '{}' to collection '{}'", src_collection, dst_collection);
+
+        let mut options = ScrollOptions {
+            limit: Some(batch_size.max(1)),
+            ..Default::default()
+        };
+        let mut copied = 0;
+        loop {
+            let page: ScrollResult = self.scroll_points(src_collection, &options).await?;
+            let mut points = page.points;
+            if !points.is_empty() {
+                points.iter_mut().for_each(&mut transform);
+                copied += points.len() as u64;
+                self.upsert_points(dst_collection, points).await?;
+            }
+
+            match page.next_page_offset {
+                Some(offset) => options.offset = Some(offset),
+                None => return Ok(copied),
+            }
+        }
+    }
+
+    /// Applies the operations in order, in a single request
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn batch_update<P: Serialize>(
+        &self,
+        collection_name: &str,
+        operations: Vec<PointOperation<P>>,
+    ) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "apply {} operations to collection '{}'", operations.len(), collection_name);
+
+        for operation in operations.iter() {
+            for id in operation.point_ids() {
+                id.validate()?;
+            }
+        }
+
+        let params = json!({
+            "operations": operations,
+        });
+        self.batch_update_api(collection_name, &params).await
+    }
+
+    /// Deletes points by their ids, e.g. `delete_points("my_test", vec![1, 4])` or `delete_points("my_test", &ids)`.
+    ///
+    /// The ids can be anything converting into `PointId`, e.g. `[1, 2, 3]`, a `Vec<String>` of UUIDs
+    /// or a slice of `PointId`s mixing both
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn delete_points(
+        &self,
+        collection_name: &str,
+        ids: impl IntoIterator<Item = impl Into<PointId>>,
+    ) -> Result<(), QdrantError> {
+        let ids: Vec<PointId> = ids.into_iter().map(Into::into).collect();
+        self.delete_points_with_options(collection_name, &ids, &WriteOptions::default())
+            .await
+    }
+
+    /// Same as `delete_points`, with the given write options
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn delete_points_with_options(
+        &self,
+        collection_name: &str,
+        ids: &[PointId],
+        options: &WriteOptions,
+    ) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "delete points from collection '{}'", collection_name);
+
+        for id in ids {
+            id.validate()?;
+        }
+
+        let mut params = json!({
+            "points": ids,
+        });
+        options.add_to(&mut params);
+        self.delete_points_api_with_query(collection_name, &params, &options.query())
+            .await
+    }
+
+    /// Deletes every point matching the filter
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn delete_points_by_filter(
+        &self,
+        collection_name: &str,
+        filter: Value,
+    ) -> Result<(), QdrantError> {
+        self.delete_points_by_filter_with_options(collection_name, filter, &WriteOptions::default())
+            .await
+    }
+
+    /// Same as `delete_points_by_filter`, with the given write options
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn delete_points_by_filter_with_options(
+        &self,
+        collection_name: &str,
+        filter: Value,
+        options: &WriteOptions,
+    ) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "delete points by filter from collection '{}'", collection_name);
+
+        let mut params = json!({
+            "filter": filter,
+        });
+        options.add_to(&mut params);
+        self.delete_points_api_with_query(collection_name, &params, &options.query())
+            .await
+    }
+
+    /// Sets the given payload keys of the points. Other keys keep their values,
+    /// use `overwrite_payload` to replace the whole payload instead.
+    ///
+    /// With a `key` path such as `"a.b"`, the keys are set in the nested object at that path
+    /// rather than at the top level of the payload.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn set_payload(
+        &self,
+        collection_name: &str,
+        payload: Map<String, Value>,
+        ids: impl IntoIterator<Item = impl Into<PointId>>,
+        key: Option<&str>,
+    ) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "set payload of points in collection '{}'", collection_name);
+
+        let ids: Vec<PointId> = ids.into_iter().map(Into::into).collect();
+        for id in ids.iter() {
+            id.validate()?;
+        }
+
+        let mut params = json!({
+            "payload": payload,
+            "points": ids,
+        });
+        if let Some(key) = key {
+            params["key"] = json!(key);
+        }
+        self.set_payload_api(collection_name, &params).await
+    }
+
+    /// Replaces the whole payload of the points, so keys missing from `payload` are removed.
+    /// Use `set_payload` to only change some keys.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn overwrite_payload(
+        &self,
+        collection_name: &str,
+        payload: Map<String, Value>,
+        ids: impl IntoIterator<Item = impl Into<PointId>>,
+    ) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "overwrite payload of points in collection '{}'", collection_name);
+
+        let ids: Vec<PointId> = ids.into_iter().map(Into::into).collect();
+        for id in ids.iter() {
+            id.validate()?;
+        }
+
+        let params = json!({
+            "payload": payload,
+            "points": ids,
+        });
+        self.overwrite_payload_api(collection_name, &params).await
+    }
+
+    /// Counts the points per distinct value of a payload field, optionally among the points matching the filter.
+    /// Returns at most `limit` values, most frequent first. The field needs a keyword, integer, bool or uuid index.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn facet(
+        &self,
+        collection_name: &str,
+        key: &str,
+        filter: Option<Value>,
+        limit: u64,
+    ) -> Result<Vec<(Value, u64)>, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "facet '{}' in collection '{}'", key, collection_name);
+
+        let mut params = json!({
+            "key": key,
+            "limit": limit,
+        });
+        if let Some(filter) = filter {
+            params["filter"] = filter;
+        }
+
+        let response: QdrantResponse<FacetResult> =
+            self.facet_request(collection_name, &params).await?;
+        Ok(response
+            .result
+            .hits
+            .into_iter()
+            .map(|hit| (hit.value, hit.count))
+            .collect())
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn create_payload_index(
+        &self,
+        collection_name: &str,
+        field_name: &str,
+        field_schema: PayloadSchemaType,
+    ) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "create payload index on field '{}' in collection '{}'", field_name, collection_name);
+
+        let params = json!({
+            "field_name": field_name,
+            "field_schema": field_schema,
+        });
+        self.create_payload_index_api(collection_name, &params)
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection = %collection_name)))]
+    pub async fn delete_payload_index(
+        &self,
+        collection_name: &str,
+        field_name: &str,
+    ) -> Result<(), QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "delete payload index on field '{}' in collection '{}'", field_name, collection_name);
+
+        self.delete_payload_index_api(collection_name, field_name)
+            .await
+    }
+
+    /// REST API functions
+    pub async fn collection_info_api(&self, collection_name: &str) -> Result<Value, QdrantError> {
+        self.collection_info_request(collection_name).await
+    }
+
+    /// Sends the get collection info request and deserializes the response body into `T` directly
+    async fn collection_info_request<T: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+    ) -> Result<T, QdrantError> {
+        let url = format!("{}/collections/{}", self.url_base, collection_name,);
+
+        let res = self.send(self.request(Method::GET, &url)).await?;
+        if !res.status().is_success() {
+            return Err(response_error(
+                res,
+                format!("get info of collection '{}'", collection_name),
+            )
+            .await);
+        }
+
+        let ci = response_json(res).await?;
+
+        Ok(ci)
+    }
+
+    pub async fn create_collection_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<bool, QdrantError> {
+        let url = format!("{}/collections/{}", self.url_base, collection_name,);
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let res = self
+            .send(self.with_body(self.request(Method::PUT, &url), body))
+            .await?;
+
+        match res.status().is_success() {
+            true => {
+                let response: QdrantResponse<bool> = response_json(res).await?;
+                Ok(response.result)
+            }
+            false => {
+                Err(response_error(res, format!("create collection '{}'", collection_name)).await)
+            }
+        }
+    }
+
+    pub async fn update_collection_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<bool, QdrantError> {
+        let url = format!("{}/collections/{}", self.url_base, collection_name,);
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let res = self
+            .send(self.with_body(self.request(Method::PATCH, &url), body))
+            .await?;
+
+        match res.status().is_success() {
+            true => {
+                let response: QdrantResponse<bool> = response_json(res).await?;
+                Ok(response.result)
+            }
+            false => {
+                Err(response_error(res, format!("update collection '{}'", collection_name)).await)
+            }
+        }
+    }
+
+    pub async fn create_shard_key_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<bool, QdrantError> {
+        let url = format!("{}/collections/{}/shards", self.url_base, collection_name,);
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let res = self
+            .send(self.with_body(self.request(Method::PUT, &url), body))
+            .await?;
+
+        match res.status().is_success() {
+            true => {
+                let response: QdrantResponse<bool> = response_json(res).await?;
+                Ok(response.result)
+            }
+            false => Err(response_error(
+                res,
+                format!("create shard key in collection '{}'", collection_name),
+            )
+            .await),
+        }
+    }
+
+    pub async fn delete_shard_key_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<bool, QdrantError> {
+        let url = format!(
+            "{}/collections/{}/shards/delete",
+            self.url_base, collection_name,
+        );
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let res = self
+            .send(self.with_body(self.request(Method::POST, &url), body))
+            .await?;
+
+        match res.status().is_success() {
+            true => {
+                let response: QdrantResponse<bool> = response_json(res).await?;
+                Ok(response.result)
+            }
+            false => Err(response_error(
+                res,
+                format!("delete shard key from collection '{}'", collection_name),
+            )
+            .await),
+        }
+    }
+
+    pub async fn cluster_info_api(&self) -> Result<Value, QdrantError> {
+        self.cluster_info_request().await
+    }
+
+    /// Sends the get cluster info request and deserializes the response body into `T` directly
+    async fn cluster_info_request<T: DeserializeOwned>(&self) -> Result<T, QdrantError> {
+        let url = format!("{}/cluster", self.url_base);
+
+        let res = self.send(self.request(Method::GET, &url)).await?;
+        if !res.status().is_success() {
+            return Err(response_error(res, "get cluster info").await);
+        }
+
+        let json = response_json(res).await?;
+
+        Ok(json)
+    }
+
+    pub async fn collection_cluster_info_api(
+        &self,
+        collection_name: &str,
+    ) -> Result<Value, QdrantError> {
+        self.collection_cluster_info_request(collection_name).await
+    }
+
+    /// Sends the get collection cluster info request and deserializes the response body into `T` directly
+    async fn collection_cluster_info_request<T: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+    ) -> Result<T, QdrantError> {
+        let url = format!("{}/collections/{}/cluster", self.url_base, collection_name,);
+
+        let res = self.send(self.request(Method::GET, &url)).await?;
+        if !res.status().is_success() {
+            return Err(response_error(
+                res,
+                format!("get cluster info of collection '{}'", collection_name),
+            )
+            .await);
+        }
+
+        let json = response_json(res).await?;
+
+        Ok(json)
+    }
+
+    pub async fn metrics_api(&self) -> Result<String, QdrantError> {
+        let url = format!("{}/metrics", self.url_base);
+
+        let res = self.send(self.request(Method::GET, &url)).await?;
+        if !res.status().is_success() {
+            return Err(response_error(res, "get metrics").await);
+        }
+
+        Ok(res.text().await?)
+    }
+
+    pub async fn telemetry_api(&self) -> Result<Value, QdrantError> {
+        self.telemetry_request().await
+    }
+
+    /// Sends the get telemetry request and deserializes the response body into `T` directly
+    async fn telemetry_request<T: DeserializeOwned>(&self) -> Result<T, QdrantError> {
+        let url = format!("{}/telemetry", self.url_base);
+
+        let res = self.send(self.request(Method::GET, &url)).await?;
+        if !res.status().is_success() {
+            return Err(response_error(res, "get telemetry").await);
+        }
+
+        let json = response_json(res).await?;
+
+        Ok(json)
+    }
+
+    pub async fn create_full_snapshot_api(&self) -> Result<Value, QdrantError> {
+        let url = format!("{}/snapshots?wait=true", self.url_base);
+
+        let res = self.send(self.request(Method::POST, &url)).await?;
+        if !res.status().is_success() {
+            return Err(response_error(res, "create full snapshot").await);
+        }
+
+        let json = response_json(res).await?;
+
+        Ok(json)
+    }
+
+    pub async fn list_full_snapshots_api(&self) -> Result<Value, QdrantError> {
+        let url = format!("{}/snapshots", self.url_base);
+
+        let res = self.send(self.request(Method::GET, &url)).await?;
+        if !res.status().is_success() {
+            return Err(response_error(res, "list full snapshots").await);
+        }
+
+        let json = response_json(res).await?;
+
+        Ok(json)
+    }
+
+    pub async fn delete_full_snapshot_api(&self, snapshot_name: &str) -> Result<(), QdrantError> {
+        let url = format!("{}/snapshots/{}?wait=true", self.url_base, snapshot_name);
+
+        let res = self.send(self.request(Method::DELETE, &url)).await?;
+        if !res.status().is_success() {
+            return Err(
+                response_error(res, format!("delete full snapshot '{}'", snapshot_name)).await,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub async fn download_full_snapshot_api(
+        &self,
+        snapshot_name: &str,
+        writer: &mut impl Write,
+    ) -> Result<u64, QdrantError> {
+        let url = format!("{}/snapshots/{}", self.url_base, snapshot_name);
+
+        let mut res = self.send(self.request(Method::GET, &url)).await?;
+        if !res.status().is_success() {
+            return Err(
+                response_error(res, format!("download full snapshot '{}'", snapshot_name)).await,
+            );
+        }
+
+        let mut size = 0;
+        while let Some(chunk) = res.chunk().await? {
+            writer.write_all(&chunk).map_err(|e| {
+                QdrantError::Unexpected(format!(
+                    "Failed to write snapshot '{}': {}",
+                    snapshot_name, e
+                ))
+            })?;
+            size += chunk.len() as u64;
+        }
+
+        Ok(size)
+    }
+
+    pub async fn recover_collection_from_snapshot_api(
+        &self,
+        collection_name: &str,
+        location: &str,
+    ) -> Result<(), QdrantError> {
+        let url = format!(
+            "{}/collections/{}/snapshots/recover?wait=true",
+            self.url_base, collection_name,
+        );
+        let body = serde_json::to_vec(&json!({ "location": location })).unwrap_or_default();
+
+        let res = self
+            .send(self.with_body(self.request(Method::PUT, &url), body))
+            .await?;
+        if !res.status().is_success() {
+            return Err(response_error(
+                res,
+                format!("recover collection '{}' from snapshot", collection_name),
+            )
+            .await);
+        }
+
+        Ok(())
+    }
+
+    /// Uploads a snapshot as the `snapshot` field of a `multipart/form-data` body
+    pub async fn upload_collection_snapshot_api(
+        &self,
+        collection_name: &str,
+        file_name: &str,
+        content: Vec<u8>,
+    ) -> Result<(), QdrantError> {
+        let url = format!(
+            "{}/collections/{}/snapshots/upload?wait=true",
+            self.url_base, collection_name,
+        );
+
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|now| now.as_nanos())
+            .unwrap_or(0);
+        let boundary = format!("qdrant-snapshot-{:x}", nanos);
+        let mut body = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"snapshot\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+            boundary,
+            file_name.replace('"', "_"),
+        )
+        .into_bytes();
+        body.extend_from_slice(&content);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+        let request = self
+            .request(Method::POST, &url)
+            .header(
+                "Content-Type",
+                format!("multipart/form-data; boundary={}", boundary),
+            )
+            .body(body);
+        let res = self.send(request).await?;
+        if !res.status().is_success() {
+            return Err(response_error(
+                res,
+                format!("upload snapshot of collection '{}'", collection_name),
+            )
+            .await);
+        }
+
+        Ok(())
+    }
+
+    pub async fn list_collections_api(&self) -> Result<Vec<String>, QdrantError> {
+        let url = format!("{}/collections", self.url_base);
+        let result = self.send(self.request(Method::GET, &url)).await;
+
+        let response = match result {
+            Ok(response) => response,
+            Err(e) => {
+                #[cfg(feature = "logging")]
+                error!(target: "stdout", "{}", e);
+
+                return Err(e);
+            }
+        };
+
+        match response.status().is_success() {
+            true => match response_json::<Value>(response).await {
+                Ok(json) => collection_names(&json),
+                Err(e) => {
+                    #[cfg(feature = "logging")]
+                    error!(target: "stdout", "{}", e);
+
+                    Err(e)
+                }
+            },
+            false => Err(response_error(response, "list collections").await),
+        }
+    }
+
+    pub async fn collection_exists_api(&self, collection_name: &str) -> Result<bool, QdrantError> {
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "check collection existence: {}", collection_name);
+
+        let url = format!("{}/collections/{}/exists", self.url_base, collection_name,);
+
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "check collection existence: {}", url);
+
+        let result = self.send(self.request(Method::GET, &url)).await;
+
+        #[cfg(feature = "logging")]
+        info!(target: "stdout", "result: {:?}", result);
+
+        let response = match result {
+            Ok(response) => response,
+            Err(e) => {
+                #[cfg(feature = "logging")]
+                error!(target: "stdout", "{}", e);
+
+                return Err(e);
+            }
+        };
 
-        let v = self.get_points_api(collection_name, &params).await.unwrap();
-        let rs: &Vec<Value> = v.get("result").unwrap().as_array().unwrap();
-        let mut ps: Vec<Point> = Vec::<Point>::new();
-        for r in rs {
-            let p: Point = serde_json::from_value(r.clone()).unwrap();
-            ps.push(p);
+        // Qdrant versions older than 1.8 have no exists endpoint
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            #[cfg(feature = "logging")]
+            warn!(target: "stdout", "[qdrant] The exists endpoint is not available, listing the collections instead");
+
+            let collection_names = self.list_collections_api().await?;
+            return Ok(collection_names.contains(&collection_name.to_string()));
         }
-        ps
-    }
 
-    pub async fn get_point(&self, collection_name: &str, id: &PointId) -> Point {
-        #[cfg(feature = "logging")]
-        info!(target: "stdout", "get point from collection '{}' with id {}", collection_name, id);
+        if !response.status().is_success() {
+            return Err(response_error(
+                response,
+                format!("check existence of collection '{}'", collection_name),
+            )
+            .await);
+        }
 
-        let v = self.get_point_api(collection_name, id).await.unwrap();
-        let r = v.get("result").unwrap();
-        serde_json::from_value(r.clone()).unwrap()
-    }
+        let json = match response_json::<Value>(response).await {
+            Ok(json) => json,
+            Err(e) => {
+                #[cfg(feature = "logging")]
+                error!(target: "stdout", "{}", e);
+
+                return Err(e);
+            }
+        };
 
-    pub async fn delete_points(&self, collection_name: &str, ids: &[PointId]) -> Result<(), Error> {
         #[cfg(feature = "logging")]
-        info!(target: "stdout", "delete points from collection '{}'", collection_name);
+        info!(target: "stdout", "json: {:?}", json);
 
-        let params = json!({
-            "points": ids,
-        });
-        self.delete_points_api(collection_name, &params).await
+        match json
+            .get("result")
+            .and_then(|result| result.get("exists"))
+            .and_then(Value::as_bool)
+        {
+            Some(exists) => Ok(exists),
+            None => Err(QdrantError::Unexpected(format!(
+                "Failed to check existence of collection '{}': the response has no boolean 'result.exists'",
+                collection_name
+            ))),
+        }
     }
 
-    /// REST API functions
-    pub async fn collection_info_api(&self, collection_name: &str) -> Result<Value, Error> {
+    pub async fn delete_collection_api(&self, collection_name: &str) -> Result<bool, QdrantError> {
         let url = format!("{}/collections/{}", self.url_base, collection_name,);
 
-        let client = reqwest::Client::new();
+        let res = self.send(self.request(Method::DELETE, &url)).await?;
 
-        let ci = match &self.api_key {
-            Some(api_key) => {
-                client
-                    .get(&url)
-                    .header("api-key", api_key)
-                    .header("Content-Type", "application/json")
-                    .send()
-                    .await?
-                    .json()
-                    .await?
+        match res.status().is_success() {
+            true => {
+                let response: QdrantResponse<bool> = response_json(res).await?;
+                Ok(response.result)
             }
-            None => {
-                client
-                    .get(&url)
-                    .header("Content-Type", "application/json")
-                    .send()
-                    .await?
-                    .json()
-                    .await?
+            false => {
+                Err(response_error(res, format!("delete collection '{}'", collection_name)).await)
             }
-        };
+        }
+    }
 
-        Ok(ci)
+    pub async fn upsert_points_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<(), QdrantError> {
+        self.upsert_points_api_with_query(collection_name, params, &[])
+            .await
+    }
+
+    /// Same as `upsert_points_api`, with extra query parameters such as `ordering`
+    pub async fn upsert_points_api_with_query(
+        &self,
+        collection_name: &str,
+        params: &Value,
+        query: &[(&str, String)],
+    ) -> Result<(), QdrantError> {
+        let url = format!(
+            "{}/collections/{}/points?wait=true",
+            self.url_base, collection_name,
+        );
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let res = self
+            .send(self.with_body(self.request(Method::PUT, &url).query(query), body))
+            .await?;
+
+        if res.status().is_success() {
+            let response: QdrantResponse<Value> = response_json(res).await?;
+            let status = response.status;
+            if status == "ok" {
+                Ok(())
+            } else {
+                Err(QdrantError::Unexpected(format!(
+                    "Failed to upsert points. Status = {}",
+                    status
+                )))
+            }
+        } else {
+            Err(response_error(res, "upsert points").await)
+        }
+    }
+
+    pub async fn batch_update_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<(), QdrantError> {
+        let url = format!(
+            "{}/collections/{}/points/batch?wait=true",
+            self.url_base, collection_name,
+        );
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let res = self
+            .send(self.with_body(self.request(Method::POST, &url), body))
+            .await?;
+
+        if res.status().is_success() {
+            let response: QdrantResponse<Value> = response_json(res).await?;
+            let status = response.status;
+            if status == "ok" {
+                Ok(())
+            } else {
+                Err(QdrantError::Unexpected(format!(
+                    "Failed to apply the batch update. Status = {}",
+                    status
+                )))
+            }
+        } else {
+            Err(response_error(res, "apply the batch update").await)
+        }
+    }
+
+    pub async fn search_points_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<Value, QdrantError> {
+        self.search_points_api_with_query(collection_name, params, &[])
+            .await
+    }
+
+    /// Same as `search_points_api`, with extra query parameters such as `consistency`
+    pub async fn search_points_api_with_query(
+        &self,
+        collection_name: &str,
+        params: &Value,
+        query: &[(&str, String)],
+    ) -> Result<Value, QdrantError> {
+        self.search_points_request(collection_name, params, query)
+            .await
+    }
+
+    /// Sends the search points request and deserializes the response body into `T` directly,
+    /// so that typed results are not first parsed into a `Value`
+    async fn search_points_request<T: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        params: &Value,
+        query: &[(&str, String)],
+    ) -> Result<T, QdrantError> {
+        let url = format!(
+            "{}/collections/{}/points/search",
+            self.url_base, collection_name,
+        );
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let response = self
+            .send(self.with_body(self.request(Method::POST, &url).query(query), body))
+            .await?;
+
+        let status_code = response.status();
+        match status_code.is_success() {
+            true => {
+                let json = response_json(response).await?;
+                Ok(json)
+            }
+            false => Err(response_error(response, "search points").await),
+        }
+    }
+
+    pub async fn search_groups_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<Value, QdrantError> {
+        self.search_groups_request(collection_name, params).await
+    }
+
+    /// Sends the search point groups request and deserializes the response body into `T` directly
+    async fn search_groups_request<T: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<T, QdrantError> {
+        let url = format!(
+            "{}/collections/{}/points/search/groups",
+            self.url_base, collection_name,
+        );
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let response = self
+            .send(self.with_body(self.request(Method::POST, &url), body))
+            .await?;
+
+        let status_code = response.status();
+        match status_code.is_success() {
+            true => {
+                let json = response_json(response).await?;
+                Ok(json)
+            }
+            false => Err(response_error(response, "search point groups").await),
+        }
+    }
+
+    pub async fn search_matrix_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<Value, QdrantError> {
+        self.search_matrix_request(collection_name, params).await
+    }
+
+    /// Sends the search distance matrix request and deserializes the response body into `T` directly
+    async fn search_matrix_request<T: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<T, QdrantError> {
+        let url = format!(
+            "{}/collections/{}/points/search/matrix/pairs",
+            self.url_base, collection_name,
+        );
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let response = self
+            .send(self.with_body(self.request(Method::POST, &url), body))
+            .await?;
+
+        let status_code = response.status();
+        match status_code.is_success() {
+            true => {
+                let json = response_json(response).await?;
+                Ok(json)
+            }
+            false => Err(response_error(response, "search distance matrix").await),
+        }
+    }
+
+    pub async fn discover_points_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<Value, QdrantError> {
+        let url = format!(
+            "{}/collections/{}/points/discover",
+            self.url_base, collection_name,
+        );
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let response = self
+            .send(self.with_body(self.request(Method::POST, &url), body))
+            .await?;
+
+        let status_code = response.status();
+        match status_code.is_success() {
+            true => {
+                let json = response_json(response).await?;
+                Ok(json)
+            }
+            false => Err(response_error(response, "discover points").await),
+        }
+    }
+
+    pub async fn recommend_points_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<Value, QdrantError> {
+        let url = format!(
+            "{}/collections/{}/points/recommend",
+            self.url_base, collection_name,
+        );
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let response = self
+            .send(self.with_body(self.request(Method::POST, &url), body))
+            .await?;
+
+        let status_code = response.status();
+        match status_code.is_success() {
+            true => {
+                let json = response_json(response).await?;
+                Ok(json)
+            }
+            false => Err(response_error(response, "recommend points").await),
+        }
+    }
+
+    pub async fn recommend_batch_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<Value, QdrantError> {
+        let url = format!(
+            "{}/collections/{}/points/recommend/batch",
+            self.url_base, collection_name,
+        );
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let response = self
+            .send(self.with_body(self.request(Method::POST, &url), body))
+            .await?;
+
+        let status_code = response.status();
+        match status_code.is_success() {
+            true => {
+                let json = response_json(response).await?;
+                Ok(json)
+            }
+            false => Err(response_error(response, "recommend points in batch").await),
+        }
+    }
+
+    pub async fn recommend_groups_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<Value, QdrantError> {
+        self.recommend_groups_request(collection_name, params).await
+    }
+
+    /// Sends the recommend point groups request and deserializes the response body into `T` directly
+    async fn recommend_groups_request<T: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<T, QdrantError> {
+        let url = format!(
+            "{}/collections/{}/points/recommend/groups",
+            self.url_base, collection_name,
+        );
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let response = self
+            .send(self.with_body(self.request(Method::POST, &url), body))
+            .await?;
+
+        let status_code = response.status();
+        match status_code.is_success() {
+            true => {
+                let json = response_json(response).await?;
+                Ok(json)
+            }
+            false => Err(response_error(response, "recommend point groups").await),
+        }
+    }
+
+    pub async fn query_points_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<Value, QdrantError> {
+        self.query_points_request(collection_name, params).await
+    }
+
+    /// Sends the query points request and deserializes the response body into `T` directly
+    async fn query_points_request<T: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<T, QdrantError> {
+        let url = format!(
+            "{}/collections/{}/points/query",
+            self.url_base, collection_name,
+        );
+
+        let body = serde_json::to_vec(params).unwrap_or_default();
+        let response = self
+            .send(self.with_body(self.request(Method::POST, &url), body))
+            .await?;
+
+        let status_code = response.status();
+        match status_code.is_success() {
+            true => {
+                let json = response_json(response).await?;
+                Ok(json)
+            }
+            false => Err(response_error(response, "query points").await),
+        }
+    }
+
+    pub async fn get_points_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<Value, QdrantError> {
+        self.get_points_api_with_query(collection_name, params, &[])
+            .await
+    }
+
+    /// Same as `get_points_api`, with extra query parameters such as `consistency`
+    pub async fn get_points_api_with_query(
+        &self,
+        collection_name: &str,
+        params: &Value,
+        query: &[(&str, String)],
+    ) -> Result<Value, QdrantError> {
+        self.get_points_request(collection_name, params, query)
+            .await
     }
 
-    pub async fn create_collection_api(
+    /// Sends the get points request and deserializes the response body into `T` directly,
+    /// so that typed results are not first parsed into a `Value`
+    async fn get_points_request<T: DeserializeOwned>(
         &self,
         collection_name: &str,
         params: &Value,
-    ) -> Result<bool, Error> {
-        let url = format!("{}/collections/{}", self.url_base, collection_name,);
+        query: &[(&str, String)],
+    ) -> Result<T, QdrantError> {
+        let url = format!("{}/collections/{}/points", self.url_base, collection_name,);
 
         let body = serde_json::to_vec(params).unwrap_or_default();
-        let client = reqwest::Client::new();
-        let res = match &self.api_key {
-            Some(api_key) => {
-                client
-                    .put(&url)
-                    .header("api-key", api_key)
-                    .header("Content-Type", "application/json")
-                    .body(body)
-                    .send()
-                    .await?
-            }
-            None => {
-                client
-                    .put(&url)
-                    .header("Content-Type", "application/json")
-                    .body(body)
-                    .send()
-                    .await?
-            }
-        };
 
-        match res.status().is_success() {
-            true => {
-                // get response body as json
-                let json = res.json::<Value>().await?;
-                let sucess = json.get("result").unwrap().as_bool().unwrap();
-                Ok(sucess)
-            }
-            false => Err(anyhow!(
-                "[qdrant] Failed to create collection: {}",
-                collection_name
-            )),
+        let res = self
+            .send(self.with_body(self.request(Method::POST, &url).query(query), body))
+            .await?;
+        if !res.status().is_success() {
+            return Err(response_error(res, "get points").await);
         }
-    }
-
-    pub async fn list_collections_api(&self) -> Result<Vec<String>, Error> {
-        let url = format!("{}/collections", self.url_base);
-        let client = reqwest::Client::new();
-        let result = match &self.api_key {
-            Some(api_key) => {
-                client
-                    .get(&url)
-                    .header("api-key", api_key)
-                    .header("Content-Type", "application/json")
-                    .send()
-                    .await
-            }
-            None => {
-                client
-                    .get(&url)
-                    .header("Content-Type", "application/json")
-                    .send()
-                    .await
-            }
-        };
-
-        let response = match result {
-            Ok(response) => response,
-            Err(e) => {
-                #[cfg(feature = "logging")]
-                error!(target: "stdout", "{}", e);
-
-                bail!("{}", e);
-            }
-        };
 
-        match response.status().is_success() {
-            true => match response.json::<Value>().await {
-                Ok(json) => match json.get("result") {
-                    Some(result) => match result.get("collections") {
-                        Some(collections) => match collections.as_array() {
-                            Some(collections) => {
-                                let mut collection_names: Vec<String> = Vec::<String>::new();
-
-                                for collection in collections {
-                                    let name = collection.get("name").unwrap().as_str().unwrap();
-                                    collection_names.push(name.to_string());
-                                }
-
-                                Ok(collection_names)
-                            },
-                            None => bail!("[qdrant] The value corresponding to the 'collections' key is not an array."),
-                        },
-                        None => bail!("[qdrant] The given key 'collections' does not exist."),
-                    },
-                    None => bail!("[qdrant] The given key 'result' does not exist."),
-                },
-                Err(e) => {
-                    #[cfg(feature = "logging")]
-                    error!(target: "stdout", "{}", e);
+        let json = response_json(res).await?;
 
-                    bail!("{}", e);
-                }
-            }
-            false => bail!("[qdrant] Failed to list collections"),
-        }
+        Ok(json)
     }
 
-    pub async fn collection_exists_api(&self, collection_name: &str) -> Result<bool, Error> {
-        #[cfg(feature = "logging")]
-        info!(target: "stdout", "check collection existence: {}", collection_name);
+    pub async fn get_point_api(
+        &self,
+        collection_name: &str,
+        id: &PointId,
+    ) -> Result<Value, QdrantError> {
+        let url = format!(
+            "{}/collections/{}/points/{}",
+            self.url_base, collection_name, id,
+        );
 
-        let url = format!("{}/collections/{}/exists", self.url_base, collection_name,);
-        let client = reqwest::Client::new();
+        let res = self.send(self.request(Method::GET, &url)).await?;
+        if !res.status().is_success() {
+            return Err(response_error(res, format!("get point {}", id)).await);
+        }
 
-        #[cfg(feature = "logging")]
-        info!(target: "stdout", "check collection existence: {}", url);
+        let json = response_json(res).await?;
 
-        let result = match &self.api_key {
-            Some(api_key) => {
-                client
-                    .get(&url)
-                    .header("api-key", api_key)
-                    .header("Content-Type", "application/json")
-                    .send()
-                    .await
-            }
-            None => {
-                client
-                    .get(&url)
-                    .header("Content-Type", "application/json")
-                    .send()
-                    .await
-            }
-        };
+        Ok(json)
+    }
 
-        #[cfg(feature = "logging")]
-        info!(target: "stdout", "result: {:?}", result);
+    pub async fn scroll_points_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<Value, QdrantError> {
+        self.scroll_points_api_with_query(collection_name, params, &[])
+            .await
+    }
 
-        let response = match result {
-            Ok(response) => response,
-            Err(e) => {
-                #[cfg(feature = "logging")]
-                error!(target: "stdout", "{}", e);
+    /// Same as `scroll_points_api`, with extra query parameters such as `consistency`
+    pub async fn scroll_points_api_with_query(
+        &self,
+        collection_name: &str,
+        params: &Value,
+        query: &[(&str, String)],
+    ) -> Result<Value, QdrantError> {
+        self.scroll_points_request(collection_name, params, query)
+            .await
+    }
 
-                bail!("{}", e);
-            }
-        };
+    /// Sends the scroll points request and deserializes the response body into `T` directly,
+    /// so that typed results are not first parsed into a `Value`
+    async fn scroll_points_request<T: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        params: &Value,
+        query: &[(&str, String)],
+    ) -> Result<T, QdrantError> {
+        let url = format!(
+            "{}/collections/{}/points/scroll",
+            self.url_base, collection_name,
+        );
 
-        let json = match response.json::<Value>().await {
-            Ok(json) => json,
-            Err(e) => {
-                #[cfg(feature = "logging")]
-                error!(target: "stdout", "{}", e);
+        let body = serde_json::to_vec(params).unwrap_or_default();
 
-                bail!("{}", e);
-            }
-        };
+        let res = self
+            .send(self.with_body(self.request(Method::POST, &url).query(query), body))
+            .await?;
+        if !res.status().is_success() {
+            return Err(response_error(res, "scroll points").await);
+        }
 
-        #[cfg(feature = "logging")]
-        info!(target: "stdout", "json: {:?}", json);
+        let json = response_json(res).await?;
 
-        match json.get("result") {
-            Some(result) => {
-                let exists = result.get("exists").unwrap().as_bool().unwrap();
-                Ok(exists)
-            }
-            None => Err(anyhow!("[qdrant] Failed to check collection existence")),
-        }
+        Ok(json)
+    }
 
-        // match res.status().is_success() {
-        //     true => {
-        //         // get response body as json
-        //         let json = res.json::<Value>().await?;
-        //         let exists = json
-        //             .get("result")
-        //             .unwrap()
-        //             .get("exists")
-        //             .unwrap()
-        //             .as_bool()
-        //             .unwrap();
-        //         Ok(exists)
-        //     }
-        //     false => Err(anyhow!("[qdrant] Failed to check collection existence")),
-        // }
+    pub async fn delete_points_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<(), QdrantError> {
+        self.delete_points_api_with_query(collection_name, params, &[])
+            .await
     }
 
-    pub async fn delete_collection_api(&self, collection_name: &str) -> Result<bool, Error> {
-        let url = format!("{}/collections/{}", self.url_base, collection_name,);
+    /// Same as `delete_points_api`, with extra query parameters such as `ordering`
+    pub async fn delete_points_api_with_query(
+        &self,
+        collection_name: &str,
+        params: &Value,
+        query: &[(&str, String)],
+    ) -> Result<(), QdrantError> {
+        let url = format!(
+            "{}/collections/{}/points/delete?wait=true",
+            self.url_base, collection_name,
+        );
 
-        let client = reqwest::Client::new();
+        let body = serde_json::to_vec(params).unwrap_or_default();
 
-        let res = match &self.api_key {
-            Some(api_key) => {
-                client
-                    .delete(&url)
-                    .header("api-key", api_key)
-                    .header("Content-Type", "application/json")
-                    .send()
-                    .await?
-            }
-            None => {
-                client
-                    .delete(&url)
-                    .header("Content-Type", "application/json")
-                    .send()
-                    .await?
-            }
-        };
+        let res = self
+            .send(self.with_body(self.request(Method::POST, &url).query(query), body))
+            .await?;
 
-        match res.status().is_success() {
-            true => {
-                // get response body as json
-                let json = res.json::<Value>().await?;
-                let sucess = json.get("result").unwrap().as_bool().unwrap();
-                Ok(sucess)
-            }
-            false => Err(anyhow!(
-                "[qdrant] Failed to delete collection: {}",
-                collection_name
-            )),
+        if res.status().is_success() {
+            Ok(())
+        } else {
+            Err(response_error(res, "delete points").await)
         }
     }
 
-    pub async fn upsert_points_api(
+    pub async fn set_payload_api(
         &self,
         collection_name: &str,
         params: &Value,
-    ) -> Result<(), Error> {
+    ) -> Result<(), QdrantError> {
         let url = format!(
-            "{}/collections/{}/points?wait=true",
+            "{}/collections/{}/points/payload?wait=true",
             self.url_base, collection_name,
         );
 
         let body = serde_json::to_vec(params).unwrap_or_default();
-        let client = reqwest::Client::new();
-        let res = match &self.api_key {
-            Some(api_key) => {
-                client
-                    .put(&url)
-                    .header("api-key", api_key)
-                    .header("Content-Type", "application/json")
-                    .body(body)
-                    .send()
-                    .await?
-            }
-            None => {
-                client
-                    .put(&url)
-                    .header("Content-Type", "application/json")
-                    .body(body)
-                    .send()
-                    .await?
-            }
-        };
+
+        let res = self
+            .send(self.with_body(self.request(Method::POST, &url), body))
+            .await?;
 
         if res.status().is_success() {
-            let v = res.json::<Value>().await?;
-            let status = v.get("status").unwrap().as_str().unwrap();
-            if status == "ok" {
-                Ok(())
-            } else {
-                Err(anyhow!(
-                    "[qdrant] Failed to upsert points. Status = {}",
-                    status
-                ))
-            }
+            Ok(())
         } else {
-            Err(anyhow!(
-                "[qdrant] Failed to upsert points: {}",
-                res.status().as_str()
-            ))
+            Err(response_error(res, "set payload").await)
         }
     }
 
-    pub async fn search_points_api(
+    pub async fn overwrite_payload_api(
         &self,
         collection_name: &str,
         params: &Value,
-    ) -> Result<Value, Error> {
+    ) -> Result<(), QdrantError> {
         let url = format!(
-            "{}/collections/{}/points/search",
+            "{}/collections/{}/points/payload?wait=true",
             self.url_base, collection_name,
         );
 
         let body = serde_json::to_vec(params).unwrap_or_default();
-        let client = reqwest::Client::new();
-        let response = match &self.api_key {
-            Some(api_key) => {
-                client
-                    .post(&url)
-                    .header("api-key", api_key)
-                    .header("Content-Type", "application/json")
-                    .body(body)
-                    .send()
-                    .await?
-            }
-            None => {
-                client
-                    .post(&url)
-                    .header("Content-Type", "application/json")
-                    .body(body)
-                    .send()
-                    .await?
-            }
-        };
 
-        let status_code = response.status();
-        match status_code.is_success() {
-            true => {
-                let json = response.json().await?;
-                Ok(json)
-            }
-            false => {
-                let status = status_code.as_str();
-                Err(anyhow!("[qdrant] Failed to search points: {}", status))
-            }
+        let res = self
+            .send(self.with_body(self.request(Method::PUT, &url), body))
+            .await?;
+
+        if res.status().is_success() {
+            Ok(())
+        } else {
+            Err(response_error(res, "overwrite payload").await)
         }
     }
 
-    pub async fn get_points_api(
+    pub async fn facet_api(
         &self,
         collection_name: &str,
         params: &Value,
-    ) -> Result<Value, Error> {
-        let url = format!("{}/collections/{}/points", self.url_base, collection_name,);
+    ) -> Result<Value, QdrantError> {
+        self.facet_request(collection_name, params).await
+    }
+
+    /// Sends the facet request and deserializes the response body into `T` directly
+    async fn facet_request<T: DeserializeOwned>(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<T, QdrantError> {
+        let url = format!("{}/collections/{}/facet", self.url_base, collection_name,);
 
         let body = serde_json::to_vec(params).unwrap_or_default();
-        let client = reqwest::Client::new();
-
-        let json = match &self.api_key {
-            Some(api_key) => {
-                client
-                    .post(&url)
-                    .header("api-key", api_key)
-                    .header("Content-Type", "application/json")
-                    .body(body)
-                    .send()
-                    .await?
-                    .json()
-                    .await?
-            }
-            None => {
-                client
-                    .post(&url)
-                    .header("Content-Type", "application/json")
-                    .body(body)
-                    .send()
-                    .await?
-                    .json()
-                    .await?
-            }
-        };
+
+        let res = self
+            .send(self.with_body(self.request(Method::POST, &url), body))
+            .await?;
+        if !res.status().is_success() {
+            return Err(response_error(res, "facet").await);
+        }
+
+        let json = response_json(res).await?;
 
         Ok(json)
     }
 
-    pub async fn get_point_api(&self, collection_name: &str, id: &PointId) -> Result<Value, Error> {
+    pub async fn create_payload_index_api(
+        &self,
+        collection_name: &str,
+        params: &Value,
+    ) -> Result<(), QdrantError> {
         let url = format!(
-            "{}/collections/{}/points/{}",
-            self.url_base, collection_name, id,
+            "{}/collections/{}/index?wait=true",
+            self.url_base, collection_name,
         );
 
-        let client = reqwest::Client::new();
+        let body = serde_json::to_vec(params).unwrap_or_default();
 
-        let json = match &self.api_key {
-            Some(api_key) => {
-                client
-                    .get(&url)
-                    .header("api-key", api_key)
-                    .header("Content-Type", "application/json")
-                    .send()
-                    .await?
-                    .json()
-                    .await?
-            }
-            None => {
-                client
-                    .get(&url)
-                    .header("Content-Type", "application/json")
-                    .send()
-                    .await?
-                    .json()
-                    .await?
-            }
-        };
+        let res = self
+            .send(self.with_body(self.request(Method::PUT, &url), body))
+            .await?;
 
-        Ok(json)
+        if res.status().is_success() {
+            Ok(())
+        } else {
+            Err(response_error(res, "create payload index").await)
+        }
     }
 
-    pub async fn delete_points_api(
+    pub async fn delete_payload_index_api(
         &self,
         collection_name: &str,
-        params: &Value,
-    ) -> Result<(), Error> {
+        field_name: &str,
+    ) -> Result<(), QdrantError> {
         let url = format!(
-            "{}/collections/{}/points/delete?wait=true",
-            self.url_base, collection_name,
+            "{}/collections/{}/index/{}?wait=true",
+            self.url_base, collection_name, field_name,
         );
 
-        let body = serde_json::to_vec(params).unwrap_or_default();
-        let client = reqwest::Client::new();
-
-        let res = match &self.api_key {
-            Some(api_key) => {
-                client
-                    .post(&url)
-                    .header("api-key", api_key)
-                    .header("Content-Type", "application/json")
-                    .body(body)
-                    .send()
-                    .await?
-            }
-            None => {
-                client
-                    .post(&url)
-                    .header("Content-Type", "application/json")
-                    .body(body)
-                    .send()
-                    .await?
-            }
-        };
+        let res = self.send(self.request(Method::DELETE, &url)).await?;
 
         if res.status().is_success() {
             Ok(())
         } else {
-            Err(anyhow!(
-                "[qdrant] Failed to delete points: {}",
-                res.status().as_str()
+            Err(response_error(res, "delete payload index").await)
+        }
+    }
+}
+
+/// Serializes the source collection of a new collection as Qdrant expects, i.e. `{"collection": name}`
+fn serialize_init_from<S: Serializer>(
+    init_from: &Option<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    init_from
+        .as_ref()
+        .map(|collection| json!({ "collection": collection }))
+        .serialize(serializer)
+}
+
+/// Reads the collection names from the response of the list collections request.
+/// A response without `collections` is a server without collections.
+fn collection_names(json: &Value) -> Result<Vec<String>, QdrantError> {
+    let result = match json.get("result") {
+        Some(result) => result,
+        None => {
+            return Err(QdrantError::Unexpected(
+                "The given key 'result' does not exist.".to_string(),
             ))
         }
+    };
+
+    let collections = match result.get("collections") {
+        Some(collections) => collections,
+        None => return Ok(vec![]),
+    };
+    let collections = match collections.as_array() {
+        Some(collections) => collections,
+        None => {
+            return Err(QdrantError::Unexpected(
+                "The value corresponding to the 'collections' key is not an array.".to_string(),
+            ))
+        }
+    };
+
+    collections
+        .iter()
+        .map(
+            |collection| match collection.get("name").and_then(Value::as_str) {
+                Some(name) => Ok(name.to_string()),
+                None => Err(QdrantError::Unexpected(
+                    "A collection has no 'name' string.".to_string(),
+                )),
+            },
+        )
+        .collect()
+}
+
+/// The longest delay a `Retry-After` header can make a retry wait
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Reads the delay a rate-limited response asks to wait before retrying, in seconds, capped at `MAX_RETRY_AFTER`.
+/// The HTTP date form of `Retry-After` is not supported.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?;
+    let seconds = value.to_str().ok()?.trim().parse::<u64>().ok()?;
+    Some(Duration::from_secs(seconds).min(MAX_RETRY_AFTER))
+}
+
+/// Returns a pseudo-random duration up to `max`, so that clients retrying at the same time spread out
+fn jitter(max: Duration) -> Duration {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|now| now.subsec_nanos())
+        .unwrap_or(0);
+    max.mul_f64(nanos as f64 / 1_000_000_000.0)
+}
+
+/// Builds the query parameters of a read with the given consistency
+fn consistency_query(consistency: Option<ReadConsistency>) -> Vec<(&'static str, String)> {
+    match consistency {
+        Some(consistency) => vec![("consistency", consistency.to_string())],
+        None => vec![],
+    }
+}
+
+/// Builds the error of a failed request from its status code and the reason in the response body
+async fn response_error(response: reqwest::Response, action: impl Display) -> QdrantError {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+
+    // Qdrant explains what went wrong in `{"status": {"error": "..."}}`
+    let message = serde_json::from_str::<Value>(&body)
+        .ok()
+        .and_then(|json| Some(json.get("status")?.get("error")?.as_str()?.to_string()))
+        .unwrap_or(body);
+
+    #[cfg(feature = "logging")]
+    error!(target: "stdout", "[qdrant] Failed to {}: {} {}", action, status.as_str(), message);
+
+    QdrantError::Http {
+        status: status.as_u16(),
+        body: message,
+        action: action.to_string(),
+    }
+}
+
+/// Waits for the duration, on tokio's timer if the `tokio` feature is enabled,
+/// or else on a timer that works with any async runtime
+async fn sleep(duration: Duration) {
+    #[cfg(feature = "tokio")]
+    tokio::time::sleep(duration).await;
+
+    #[cfg(not(feature = "tokio"))]
+    futures_timer::Delay::new(duration).await;
+}
+
+/// Checks that the vectors of the point have the sizes of the vector spaces of the collection
+fn check_vector_sizes<P>(
+    collection_name: &str,
+    config: &VectorsConfig,
+    point: &Point<P>,
+) -> Result<(), QdrantError> {
+    let check = |name: Option<&str>, len: usize| {
+        let params = match (config, name) {
+            (VectorsConfig::Single(params), None) => params,
+            (VectorsConfig::Multi(spaces), Some(name)) => match spaces.get(name) {
+                Some(params) => params,
+                None => {
+                    return Err(QdrantError::InvalidInput(format!(
+                        "Point {} has a vector named '{}', but collection '{}' has no dense vector space of that name",
+                        point.id, name, collection_name
+                    )))
+                }
+            },
+            (VectorsConfig::Single(_), Some(name)) => {
+                return Err(QdrantError::InvalidInput(format!(
+                    "Point {} has a vector named '{}', but collection '{}' has a single unnamed vector space",
+                    point.id, name, collection_name
+                )))
+            }
+            (VectorsConfig::Multi(_), None) => {
+                return Err(QdrantError::InvalidInput(format!(
+                    "Point {} has an unnamed vector, but collection '{}' has named vector spaces",
+                    point.id, collection_name
+                )))
+            }
+        };
+        if len as u64 != params.size {
+            return Err(QdrantError::InvalidInput(format!(
+                "Point {} has a vector{} of length {}, but collection '{}' expects {}",
+                point.id,
+                name.map(|name| format!(" '{}'", name)).unwrap_or_default(),
+                len,
+                collection_name,
+                params.size
+            )));
+        }
+        Ok(())
+    };
+
+    match &point.vector {
+        None => Ok(()),
+        Some(VectorData::Dense(vector)) => check(None, vector.len()),
+        Some(VectorData::Named(vectors)) => {
+            for (name, vector) in vectors {
+                if let Vector::Dense(vector) = vector {
+                    check(Some(name), vector.len())?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Deserializes the `result` of a Qdrant response, without copying it
+fn take_result<T: DeserializeOwned>(json: Value) -> Result<T, QdrantError> {
+    let response: QdrantResponse<T> = serde_json::from_value(json)?;
+    Ok(response.result)
+}
+
+/// The body of a request getting points by id
+fn get_points_params(ids: &[PointId], options: &GetPointsOptions) -> Value {
+    json!({
+        "ids": ids,
+        "with_payload": options.with_payload,
+        "with_vector": options.with_vector,
+    })
+}
+
+/// Reads the response body and deserializes it from JSON
+async fn response_json<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, QdrantError> {
+    let bytes = response.bytes().await?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collection_names_of_an_empty_server() {
+        let json = json!({ "result": { "collections": [] }, "status": "ok", "time": 0.0 });
+        assert!(collection_names(&json).unwrap().is_empty());
+
+        let json = json!({ "result": {}, "status": "ok", "time": 0.0 });
+        assert!(collection_names(&json).unwrap().is_empty());
+    }
+
+    #[test]
+    fn collection_names_of_a_server_with_collections() {
+        let json = json!({
+            "result": { "collections": [{ "name": "a" }, { "name": "b" }] },
+            "status": "ok",
+            "time": 0.0,
+        });
+        assert_eq!(collection_names(&json).unwrap(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn collection_names_of_an_error_response() {
+        let json = json!({ "status": { "error": "Service unavailable" }, "time": 0.0 });
+        assert!(collection_names(&json).is_err());
+    }
+
+    #[test]
+    fn point_ids_of_numeric_strings_are_numbers() {
+        assert_eq!(PointId::parse("42"), PointId::Num(42));
+        let id: PointId = serde_json::from_value(json!("42")).unwrap();
+        assert_eq!(id, PointId::Num(42));
+        let id: PointId = serde_json::from_value(json!(42)).unwrap();
+        assert_eq!(id, PointId::Num(42));
+    }
+
+    #[test]
+    fn point_ids_of_other_strings_stay_strings() {
+        let uuid = "6f9d3b1c-3b7e-4c1a-9d2e-8f0a1b2c3d4e";
+        let id: PointId = serde_json::from_value(json!(uuid)).unwrap();
+        assert_eq!(id, PointId::Uuid(uuid.to_string()));
+
+        // one more than u64::MAX
+        let overflow = "18446744073709551616";
+        assert_eq!(
+            PointId::parse(overflow),
+            PointId::Uuid(overflow.to_string())
+        );
+        let id: PointId = serde_json::from_value(json!(overflow)).unwrap();
+        assert_eq!(id, PointId::Uuid(overflow.to_string()));
+    }
+
+    #[test]
+    fn retry_after_is_capped_and_ignores_invalid_values() {
+        let headers = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                reqwest::header::RETRY_AFTER,
+                HeaderValue::from_static(value),
+            );
+            headers
+        };
+
+        assert_eq!(retry_after(&headers("2")), Some(Duration::from_secs(2)));
+        assert_eq!(retry_after(&headers("86400")), Some(MAX_RETRY_AFTER));
+        assert_eq!(retry_after(&headers("soon")), None);
+        assert_eq!(retry_after(&headers("Wed, 21 Oct 2015 07:28:00 GMT")), None);
+        assert_eq!(retry_after(&HeaderMap::new()), None);
     }
 }
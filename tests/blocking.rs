@@ -0,0 +1,56 @@
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use common::MockServer;
+use qdrant::{Point, QdrantBuilder};
+use serde_json::json;
+
+/// Starts the mock server on a runtime of its own thread, as the blocking client must not run inside one
+fn start_server() -> MockServer {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async move {
+            sender.send(MockServer::start().await).unwrap();
+            std::future::pending::<()>().await
+        });
+    });
+    receiver.recv().unwrap()
+}
+
+#[test]
+fn blocking_client_sends_the_requests_of_the_async_client() {
+    let server = start_server();
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "exists": true }, "status": "ok", "time": 0.0 }),
+    );
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "operation_id": 1, "status": "completed" }, "status": "ok", "time": 0.0 }),
+    );
+
+    let client =
+        qdrant::blocking::Qdrant::from_builder(QdrantBuilder::new().url(server.url())).unwrap();
+    assert!(client.collection_exists("test").unwrap());
+    let points: Vec<Point> = vec![Point::builder()
+        .id(1)
+        .vector(vec![0.1, 0.2])
+        .build()
+        .unwrap()];
+    client.upsert_points("test", points).unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/collections/test/exists");
+    assert_eq!(requests[1].method, "PUT");
+    assert_eq!(requests[1].path, "/collections/test/points?wait=true");
+    assert_eq!(requests[1].json()["points"][0]["id"], 1);
+}
@@ -0,0 +1,1559 @@
+mod common;
+
+use common::MockServer;
+use qdrant::*;
+use serde_json::json;
+
+fn client(server: &MockServer) -> Qdrant {
+    QdrantBuilder::new()
+        .url(server.url())
+        .api_key("secret")
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn create_collection_checks_existence_then_sends_the_vectors_config() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "exists": false }, "status": "ok", "time": 0.0 }),
+    );
+    server.respond(
+        200,
+        &[],
+        json!({ "result": true, "status": "ok", "time": 0.1 }),
+    );
+
+    client(&server).create_collection("test", 4).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/collections/test/exists");
+    assert_eq!(requests[0].header("api-key"), Some("secret"));
+    assert_eq!(requests[0].header("content-type"), None);
+
+    assert_eq!(requests[1].method, "PUT");
+    assert_eq!(requests[1].path, "/collections/test");
+    assert_eq!(requests[1].header("api-key"), Some("secret"));
+    assert_eq!(requests[1].header("content-type"), Some("application/json"));
+    assert_eq!(
+        requests[1].json()["vectors"],
+        json!({ "size": 4, "distance": "Cosine", "on_disk": true })
+    );
+}
+
+#[tokio::test]
+async fn create_collection_fails_if_it_exists() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "exists": true }, "status": "ok", "time": 0.0 }),
+    );
+
+    let err = client(&server)
+        .create_collection("test", 4)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, QdrantError::CollectionExists(name) if name == "test"));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn delete_collection_if_exists_ignores_missing_collections() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "exists": false }, "status": "ok", "time": 0.0 }),
+    );
+
+    client(&server)
+        .delete_collection_if_exists("test")
+        .await
+        .unwrap();
+
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn list_collections_parses_the_names() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({
+            "result": { "collections": [{ "name": "a" }, { "name": "b" }] },
+            "status": "ok",
+            "time": 0.0,
+        }),
+    );
+
+    let names = client(&server).list_collections().await.unwrap();
+
+    assert_eq!(names, vec!["a", "b"]);
+    assert_eq!(server.requests()[0].path, "/collections");
+}
+
+#[tokio::test]
+async fn upsert_points_waits_for_the_write() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "operation_id": 1, "status": "completed" }, "status": "ok", "time": 0.0 }),
+    );
+
+    let points: Vec<Point> = vec![Point {
+        id: PointId::Num(1),
+        vector: Some(vec![0.1, 0.2].into()),
+        payload: json!({ "city": "Berlin" }).as_object().cloned(),
+    }];
+    client(&server).upsert_points("test", points).await.unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "PUT");
+    assert_eq!(request.path, "/collections/test/points?wait=true");
+    assert_eq!(
+        request.json(),
+        json!({
+            "points": [{ "id": 1, "vector": [0.1f32, 0.2f32], "payload": { "city": "Berlin" } }],
+        })
+    );
+}
+
+#[tokio::test]
+async fn search_points_sends_the_query_and_parses_the_hits() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({
+            "result": [
+                { "id": 1, "version": 0, "score": 0.9, "payload": { "city": "Berlin" } },
+                { "id": "6f9d3b1c-3b7e-4c1a-9d2e-8f0a1b2c3d4e", "version": 0, "score": 0.5 },
+            ],
+            "status": "ok",
+            "time": 0.002,
+        }),
+    );
+
+    let options = SearchOptions {
+        score_threshold: Some(0.4),
+        using: Some("text".to_string()),
+        ..Default::default()
+    };
+    let response = client(&server)
+        .search_points_response::<serde_json::Map<String, serde_json::Value>>(
+            "test",
+            vec![0.1, 0.2],
+            2,
+            &options,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.time, 0.002);
+    let hits = response.result;
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].id, PointId::Num(1));
+    assert_eq!(hits[0].payload.as_ref().unwrap()["city"], "Berlin");
+    assert_eq!(
+        hits[1].id,
+        PointId::Uuid("6f9d3b1c-3b7e-4c1a-9d2e-8f0a1b2c3d4e".to_string())
+    );
+    assert!(hits[1].payload.is_none());
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.path, "/collections/test/points/search");
+    let body = request.json();
+    assert_eq!(
+        body["vector"],
+        json!({ "name": "text", "vector": [0.1f32, 0.2f32] })
+    );
+    assert_eq!(body["limit"], 2);
+    assert_eq!(body["score_threshold"], json!(0.4f32));
+    assert_eq!(body["with_payload"], true);
+    assert_eq!(body["with_vector"], true);
+}
+
+#[tokio::test]
+async fn get_points_ordered_aligns_the_points_with_the_ids() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({
+            "result": [{ "id": 3, "payload": {} }, { "id": 1, "payload": {} }],
+            "status": "ok",
+            "time": 0.0,
+        }),
+    );
+
+    let points: Vec<Option<Point>> = client(&server)
+        .get_points_ordered("test", vec![1, 2, 3, 1])
+        .await
+        .unwrap();
+
+    let ids: Vec<Option<PointId>> = points
+        .into_iter()
+        .map(|point| point.map(|point| point.id))
+        .collect();
+    assert_eq!(
+        ids,
+        vec![
+            Some(PointId::Num(1)),
+            None,
+            Some(PointId::Num(3)),
+            Some(PointId::Num(1))
+        ]
+    );
+
+    let request = &server.requests()[0];
+    assert_eq!(request.path, "/collections/test/points");
+    assert_eq!(request.json()["ids"], json!([1, 2, 3]));
+}
+
+#[tokio::test]
+async fn errors_carry_the_reason_reported_by_qdrant() {
+    let server = MockServer::start().await;
+    server.respond(
+        400,
+        &[],
+        json!({ "status": { "error": "Wrong input: Vector dimension error" }, "time": 0.0 }),
+    );
+
+    let err = client(&server)
+        .search_points::<serde_json::Map<String, serde_json::Value>>("test", vec![0.1], 1, None)
+        .await
+        .unwrap_err();
+
+    match err {
+        QdrantError::Http {
+            status,
+            ref body,
+            ref action,
+        } => {
+            assert_eq!(status, 400);
+            assert_eq!(body, "Wrong input: Vector dimension error");
+            assert_eq!(action, "search points");
+            assert_eq!(
+                err.to_string(),
+                "[qdrant] Failed to search points, status 400: Wrong input: Vector dimension error"
+            );
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[tokio::test]
+async fn server_errors_are_retried() {
+    let server = MockServer::start().await;
+    server.respond(
+        503,
+        &[],
+        json!({ "status": { "error": "unavailable" }, "time": 0.0 }),
+    );
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "exists": true }, "status": "ok", "time": 0.0 }),
+    );
+
+    let client = QdrantBuilder::new()
+        .url(server.url())
+        .retries(1)
+        .build()
+        .unwrap();
+    assert!(client.collection_exists("test").await.unwrap());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn replayed_upserts_send_the_same_points() {
+    let server = MockServer::start().await;
+    let upserted = json!({ "result": { "operation_id": 1, "status": "completed" }, "status": "ok", "time": 0.0 });
+    // the first upsert times out on the server side and is retried
+    server.respond(
+        503,
+        &[],
+        json!({ "status": { "error": "timeout" }, "time": 0.0 }),
+    );
+    server.respond(200, &[], upserted.clone());
+    server.respond(200, &[], upserted);
+
+    let client = QdrantBuilder::new()
+        .url(server.url())
+        .retries(1)
+        .build()
+        .unwrap();
+    let points = vec![
+        Point::builder()
+            .id(1)
+            .vector(vec![0.1, 0.2])
+            .build()
+            .unwrap(),
+        Point::builder()
+            .id(2)
+            .vector(vec![0.3, 0.4])
+            .build()
+            .unwrap(),
+    ];
+
+    client.upsert_points("test", points.clone()).await.unwrap();
+    client.upsert_points("test", points).await.unwrap();
+
+    // the retried and the replayed upserts carry the same ids, so Qdrant overwrites instead of duplicating
+    let upserts = server.requests();
+    assert_eq!(upserts.len(), 3);
+    assert!(upserts
+        .iter()
+        .all(|r| r.method == "PUT" && r.path == "/collections/test/points?wait=true"));
+    assert!(upserts.iter().all(|r| r.body == upserts[0].body));
+    assert_eq!(upserts[0].json()["points"][1]["id"], 2);
+}
+
+#[tokio::test]
+async fn rate_limited_requests_are_retried_after_the_given_delay() {
+    let server = MockServer::start().await;
+    server.respond(
+        429,
+        &[("retry-after", "0")],
+        json!({ "status": { "error": "Too many requests" }, "time": 0.0 }),
+    );
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "exists": true }, "status": "ok", "time": 0.0 }),
+    );
+
+    let client = QdrantBuilder::new()
+        .url(server.url())
+        .retries(1)
+        .build()
+        .unwrap();
+    let started = std::time::Instant::now();
+    assert!(client.collection_exists("test").await.unwrap());
+
+    // the backoff of the first retry would be 200ms, Retry-After asks for none
+    assert!(started.elapsed() < std::time::Duration::from_millis(200));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn large_integer_payload_values_round_trip_exactly() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "operation_id": 1, "status": "completed" }, "status": "ok", "time": 0.0 }),
+    );
+    let client = client(&server);
+
+    let payload = json!({ "max": u64::MAX, "min": i64::MIN, "id": 9007199254740993u64 });
+    let points: Vec<Point> = vec![Point {
+        id: PointId::Num(1),
+        vector: None,
+        payload: payload.as_object().cloned(),
+    }];
+    client.upsert_points("test", points).await.unwrap();
+
+    let body = String::from_utf8(server.requests()[0].body.clone()).unwrap();
+    assert!(body.contains("18446744073709551615"));
+    assert!(body.contains("-9223372036854775808"));
+    assert!(body.contains("9007199254740993"));
+
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "id": 1, "payload": payload }, "status": "ok", "time": 0.0 }),
+    );
+    let point: Point = client.get_point("test", 1).await.unwrap();
+    let read = point.payload.unwrap();
+    assert_eq!(read["max"].as_u64(), Some(u64::MAX));
+    assert_eq!(read["min"].as_i64(), Some(i64::MIN));
+    assert_eq!(read["id"].as_u64(), Some(9007199254740993));
+}
+
+#[tokio::test]
+async fn upsert_points_sends_the_shard_key_with_the_batch() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "operation_id": 1, "status": "completed" }, "status": "ok", "time": 0.0 }),
+    );
+
+    let points: Vec<Point> = vec![
+        Point {
+            id: PointId::Num(1),
+            vector: Some(vec![0.1].into()),
+            payload: None,
+        },
+        Point {
+            id: PointId::Num(2),
+            vector: Some(vec![0.2].into()),
+            payload: None,
+        },
+    ];
+    let options = WriteOptions {
+        ordering: Some(WriteOrdering::Strong),
+        shard_key: Some(ShardKey::from("tenant-a").into()),
+        ..Default::default()
+    };
+    client(&server)
+        .upsert_points_with_options("test", points, &options)
+        .await
+        .unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(
+        request.path,
+        "/collections/test/points?wait=true&ordering=strong"
+    );
+    let body = request.json();
+    assert_eq!(body["shard_key"], "tenant-a");
+    assert_eq!(body["points"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn points_without_payload_or_vector_leave_them_out() {
+    let point = Point::builder().id(7).build().unwrap();
+
+    assert_eq!(serde_json::to_value(&point).unwrap(), json!({ "id": 7 }));
+}
+
+#[tokio::test]
+async fn upsert_points_can_check_the_vector_sizes() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({
+            "result": {
+                "status": "green",
+                "optimizer_status": "ok",
+                "points_count": 0,
+                "segments_count": 1,
+                "config": { "params": { "vectors": { "size": 4, "distance": "Cosine" } } },
+            },
+            "status": "ok",
+            "time": 0.0,
+        }),
+    );
+
+    let points = vec![
+        Point::builder()
+            .id(1)
+            .vector(vec![0.1, 0.2, 0.3, 0.4])
+            .build()
+            .unwrap(),
+        Point::builder()
+            .id(2)
+            .vector(vec![0.1, 0.2, 0.3])
+            .build()
+            .unwrap(),
+    ];
+    let options = WriteOptions {
+        check_vector_sizes: true,
+        ..Default::default()
+    };
+    let err = client(&server)
+        .upsert_points_with_options("test", points, &options)
+        .await
+        .unwrap_err();
+
+    match err {
+        QdrantError::InvalidInput(msg) => assert_eq!(
+            msg,
+            "Point 2 has a vector of length 3, but collection 'test' expects 4"
+        ),
+        e => panic!("unexpected error: {}", e),
+    }
+    // only the collection info was requested
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn upsert_points_rejects_vectors_of_unknown_vector_spaces() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({
+            "result": {
+                "status": "green",
+                "optimizer_status": "ok",
+                "points_count": 0,
+                "segments_count": 1,
+                "config": { "params": { "vectors": { "text": { "size": 2, "distance": "Cosine" } } } },
+            },
+            "status": "ok",
+            "time": 0.0,
+        }),
+    );
+
+    let point: Point = Point {
+        id: PointId::Num(1),
+        vector: Some(VectorData::Named(
+            [("txet".to_string(), Vector::Dense(vec![0.1, 0.2]))].into(),
+        )),
+        payload: None,
+    };
+    let options = WriteOptions {
+        check_vector_sizes: true,
+        ..Default::default()
+    };
+    let err = client(&server)
+        .upsert_points_with_options("test", vec![point], &options)
+        .await
+        .unwrap_err();
+
+    match err {
+        QdrantError::InvalidInput(msg) => assert_eq!(
+            msg,
+            "Point 1 has a vector named 'txet', but collection 'test' has no dense vector space of that name"
+        ),
+        e => panic!("unexpected error: {}", e),
+    }
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn delete_points_takes_any_kind_of_ids() {
+    let server = MockServer::start().await;
+    let uuid = "6f9d3b1c-3b7e-4c1a-9d2e-8f0a1b2c3d4e";
+    let client = client(&server);
+
+    server.respond(
+        200,
+        &[],
+        json!({ "result": {}, "status": "ok", "time": 0.0 }),
+    );
+    client.delete_points("test", [1u64, 2, 3]).await.unwrap();
+
+    server.respond(
+        200,
+        &[],
+        json!({ "result": {}, "status": "ok", "time": 0.0 }),
+    );
+    client
+        .delete_points("test", vec![uuid.to_string()])
+        .await
+        .unwrap();
+
+    server.respond(
+        200,
+        &[],
+        json!({ "result": {}, "status": "ok", "time": 0.0 }),
+    );
+    client
+        .delete_points("test", &[PointId::Num(4), PointId::from(uuid)])
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(
+        requests[0].path,
+        "/collections/test/points/delete?wait=true"
+    );
+    assert_eq!(requests[0].json(), json!({ "points": [1, 2, 3] }));
+    assert_eq!(requests[1].json(), json!({ "points": [uuid] }));
+    assert_eq!(requests[2].json(), json!({ "points": [4, uuid] }));
+}
+
+#[tokio::test]
+async fn oversized_request_bodies_are_not_sent() {
+    let server = MockServer::start().await;
+    let client = QdrantBuilder::new()
+        .url(server.url())
+        .max_body_size(1024)
+        .build()
+        .unwrap();
+
+    let points: Vec<Point> = (0..100u64)
+        .map(|id| {
+            Point::builder()
+                .id(id)
+                .vector(vec![0.5; 16])
+                .build()
+                .unwrap()
+        })
+        .collect();
+    let err = client.upsert_points("test", points).await.unwrap_err();
+
+    assert!(matches!(err, QdrantError::InvalidInput(msg) if msg.contains("upsert_points_iter")));
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn search_groups_can_look_up_a_point_per_group() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({
+            "result": {
+                "groups": [{
+                    "id": 7,
+                    "hits": [{ "id": 70, "version": 0, "score": 0.9 }],
+                    "lookup": { "id": 7, "payload": { "title": "Rust" } },
+                }]
+            },
+            "status": "ok",
+            "time": 0.0,
+        }),
+    );
+
+    #[derive(serde::Deserialize)]
+    struct Doc {
+        title: String,
+    }
+
+    let groups: Vec<PointGroup<serde_json::Map<String, serde_json::Value>, Doc>> = client(&server)
+        .search_groups_with_lookup(
+            "chunks",
+            vec![0.1, 0.2],
+            "doc_id",
+            1,
+            10,
+            None,
+            Some(WithLookup::new("docs")),
+        )
+        .await
+        .unwrap();
+
+    let lookup = groups[0].lookup.as_ref().unwrap();
+    assert_eq!(lookup.id, PointId::Num(7));
+    assert_eq!(lookup.payload.as_ref().unwrap().title, "Rust");
+
+    let request = &server.requests()[0];
+    assert_eq!(request.path, "/collections/chunks/points/search/groups");
+    assert_eq!(
+        request.json()["with_lookup"],
+        json!({ "collection": "docs", "with_payload": true, "with_vectors": false })
+    );
+}
+
+#[tokio::test]
+async fn snapshots_are_recovered_from_urls_and_uploaded_from_files() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": true, "status": "ok", "time": 0.0 }),
+    );
+    server.respond(
+        200,
+        &[],
+        json!({ "result": true, "status": "ok", "time": 0.0 }),
+    );
+
+    // unique per process, as concurrent test runs share the temp dir
+    let file_name = format!("qdrant-recover-test-{}.snapshot", std::process::id());
+    let path = std::env::temp_dir().join(&file_name);
+    std::fs::write(&path, b"snapshot bytes").unwrap();
+
+    let qdrant = client(&server);
+    qdrant
+        .recover_collection_from_snapshot("test", "https://backups.example.com/test.snapshot")
+        .await
+        .unwrap();
+    qdrant
+        .recover_collection_from_snapshot("test", path.clone())
+        .await
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "PUT");
+    assert_eq!(
+        requests[0].path,
+        "/collections/test/snapshots/recover?wait=true"
+    );
+    assert_eq!(
+        requests[0].json(),
+        json!({ "location": "https://backups.example.com/test.snapshot" })
+    );
+
+    assert_eq!(requests[1].method, "POST");
+    assert_eq!(
+        requests[1].path,
+        "/collections/test/snapshots/upload?wait=true"
+    );
+    assert!(requests[1]
+        .header("content-type")
+        .unwrap()
+        .starts_with("multipart/form-data; boundary="));
+    let body = String::from_utf8_lossy(&requests[1].body);
+    assert!(body.contains(&format!(r#"name="snapshot"; filename="{}""#, file_name)));
+    assert!(body.contains("snapshot bytes"));
+}
+
+#[tokio::test]
+async fn scroll_points_can_order_by_a_payload_field() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({
+            "result": { "points": [{ "id": 3, "payload": { "ts": 30 } }], "next_page_offset": null },
+            "status": "ok",
+            "time": 0.0,
+        }),
+    );
+
+    let options = ScrollOptions {
+        limit: Some(1),
+        order_by: Some(OrderBy {
+            start_from: Some(json!(40)),
+            ..OrderBy::new("ts", Direction::Desc)
+        }),
+        ..Default::default()
+    };
+    let page: ScrollResult = client(&server)
+        .scroll_points("events", &options)
+        .await
+        .unwrap();
+    assert_eq!(page.points[0].id, PointId::Num(3));
+
+    let request = &server.requests()[0];
+    assert_eq!(request.path, "/collections/events/points/scroll");
+    assert_eq!(
+        request.json()["order_by"],
+        json!({ "key": "ts", "direction": "desc", "start_from": 40 })
+    );
+}
+
+#[tokio::test]
+async fn search_timeout_is_sent_in_the_query_string() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": [], "status": "ok", "time": 0.0 }),
+    );
+
+    let options = SearchOptions {
+        consistency: Some(ReadConsistency::All),
+        timeout: Some(5),
+        ..Default::default()
+    };
+    let hits: Vec<ScoredPoint> = client(&server)
+        .search_points_with_options("test", vec![0.1, 0.2], 10, &options)
+        .await
+        .unwrap();
+    assert!(hits.is_empty());
+
+    let request = &server.requests()[0];
+    assert_eq!(
+        request.path,
+        "/collections/test/points/search?consistency=all&timeout=5"
+    );
+    assert!(request.json().get("timeout").is_none());
+}
+
+#[tokio::test]
+async fn batch_update_validates_the_ids_of_every_operation() {
+    let server = MockServer::start().await;
+
+    let operations: Vec<PointOperation> = vec![
+        PointOperation::Delete {
+            points: vec![PointId::Num(1)],
+        },
+        PointOperation::ClearPayload {
+            points: vec![PointId::Uuid("not-a-uuid".to_string())],
+        },
+    ];
+    let result = client(&server).batch_update("test", operations).await;
+
+    assert!(matches!(result, Err(QdrantError::InvalidInput(_))));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn indexed_ratio_counts_every_named_vector() {
+    let info: CollectionInfo = serde_json::from_value(json!({
+        "status": "yellow",
+        "optimizer_status": "ok",
+        "indexed_vectors_count": 10,
+        "points_count": 10,
+        "segments_count": 1,
+        "config": {
+            "params": {
+                "vectors": {
+                    "image": { "size": 4, "distance": "Cosine" },
+                    "text": { "size": 4, "distance": "Dot" },
+                }
+            }
+        },
+    }))
+    .unwrap();
+
+    assert_eq!(info.indexed_ratio(), Some(0.5));
+}
+
+#[tokio::test]
+async fn scrolls_with_a_zero_page_size_fetch_one_point_at_a_time() {
+    let server = MockServer::start().await;
+    let last_page = json!({
+        "result": { "points": [], "next_page_offset": null },
+        "status": "ok",
+        "time": 0.0,
+    });
+    server.respond(200, &[], last_page.clone());
+    server.respond(200, &[], last_page);
+
+    let client = client(&server);
+    assert_eq!(client.copy_points("src", "dst", 0).await.unwrap(), 0);
+    let points: Vec<Result<Point, QdrantError>> =
+        futures::StreamExt::collect(client.scroll_stream("src", None, 0)).await;
+    assert!(points.is_empty());
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests.iter().all(|r| r.json()["limit"] == 1));
+}
+
+#[tokio::test]
+async fn try_get_point_takes_any_kind_of_id() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": [], "status": "ok", "time": 0.0 }),
+    );
+    server.respond(
+        200,
+        &[],
+        json!({ "result": [{ "id": 8 }], "status": "ok", "time": 0.0 }),
+    );
+
+    let client = client(&server);
+    let missing: Option<Point> = client.try_get_point("test", 7).await.unwrap();
+    assert!(missing.is_none());
+    let found: Option<Point> = client
+        .try_get_point("test", &PointId::Num(8))
+        .await
+        .unwrap();
+    assert_eq!(found.unwrap().id, PointId::Num(8));
+
+    let requests = server.requests();
+    assert_eq!(requests[0].json()["ids"], json!([7]));
+    assert_eq!(requests[1].json()["ids"], json!([8]));
+}
+
+#[tokio::test]
+async fn base_urls_keep_their_path_prefix_without_doubling_slashes() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "collections": [] }, "status": "ok", "time": 0.0 }),
+    );
+
+    let client = QdrantBuilder::new()
+        .url(format!("{}/qdrant/", server.url()))
+        .build()
+        .unwrap();
+    assert!(client.list_collections().await.unwrap().is_empty());
+
+    assert_eq!(server.requests()[0].path, "/qdrant/collections");
+}
+
+#[tokio::test]
+async fn ping_checks_the_health_endpoint_once() {
+    let server = MockServer::start().await;
+    server.respond(200, &[], json!("healthz check passed"));
+    server.respond(
+        503,
+        &[],
+        json!({ "status": { "error": "unavailable" }, "time": 0.0 }),
+    );
+
+    let client = QdrantBuilder::new()
+        .url(server.url())
+        .retries(2)
+        .build()
+        .unwrap();
+    assert!(client.ping().await.unwrap() < std::time::Duration::from_secs(5));
+    match client.ping().await.unwrap_err() {
+        QdrantError::Http { status, .. } => assert_eq!(status, 503),
+        e => panic!("unexpected error: {}", e),
+    }
+
+    // the failed ping is not retried
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests
+        .iter()
+        .all(|r| r.method == "GET" && r.path == "/healthz"));
+}
+
+#[tokio::test]
+async fn full_snapshots_are_created_listed_downloaded_and_deleted() {
+    let server = MockServer::start().await;
+    let snapshot = json!({
+        "name": "full-snapshot-2024-01-01.snapshot",
+        "creation_time": "2024-01-01T00:00:00",
+        "size": 1024,
+        "checksum": "abc",
+    });
+    server.respond(
+        200,
+        &[],
+        json!({ "result": snapshot, "status": "ok", "time": 0.1 }),
+    );
+    server.respond(
+        200,
+        &[],
+        json!({ "result": [snapshot], "status": "ok", "time": 0.0 }),
+    );
+    server.respond(200, &[], json!("snapshot bytes"));
+    server.respond(
+        200,
+        &[],
+        json!({ "result": true, "status": "ok", "time": 0.0 }),
+    );
+
+    let client = client(&server);
+    let created = client.create_full_snapshot().await.unwrap();
+    assert_eq!(created.name, "full-snapshot-2024-01-01.snapshot");
+    assert_eq!(created.size, 1024);
+
+    let snapshots = client.list_full_snapshots().await.unwrap();
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(snapshots[0].checksum.as_deref(), Some("abc"));
+
+    let mut file = Vec::new();
+    let size = client
+        .download_full_snapshot(&created.name, &mut file)
+        .await
+        .unwrap();
+    assert_eq!(file, br#""snapshot bytes""#);
+    assert_eq!(size, file.len() as u64);
+
+    client.delete_full_snapshot(&created.name).await.unwrap();
+
+    let requests = server.requests();
+    let calls: Vec<(&str, &str)> = requests
+        .iter()
+        .map(|r| (r.method.as_str(), r.path.as_str()))
+        .collect();
+    assert_eq!(
+        calls,
+        vec![
+            ("POST", "/snapshots?wait=true"),
+            ("GET", "/snapshots"),
+            ("GET", "/snapshots/full-snapshot-2024-01-01.snapshot"),
+            (
+                "DELETE",
+                "/snapshots/full-snapshot-2024-01-01.snapshot?wait=true"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn builder_debug_output_redacts_credentials() {
+    let builder = QdrantBuilder::new()
+        .url("http://localhost:6333")
+        .api_key("secret-key")
+        .bearer_token("secret-token");
+    let debug = format!("{:?}", builder);
+
+    assert!(debug.contains("http://localhost:6333"));
+    assert!(debug.contains("<redacted>"));
+    assert!(!debug.contains("secret-key"));
+    assert!(!debug.contains("secret-token"));
+}
+
+#[tokio::test]
+async fn points_exist_takes_any_kind_of_ids() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": [{ "id": 3 }, { "id": 1 }], "status": "ok", "time": 0.0 }),
+    );
+
+    let exist = client(&server)
+        .points_exist("test", vec![1, 2, 3])
+        .await
+        .unwrap();
+    assert_eq!(exist, vec![true, false, true]);
+
+    let body = server.requests()[0].json();
+    assert_eq!(body["ids"], json!([1, 2, 3]));
+    assert_eq!(body["with_payload"], false);
+    assert_eq!(body["with_vector"], false);
+}
+
+#[tokio::test]
+async fn get_point_reads_numeric_string_ids_as_numbers() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "id": "7", "payload": { "city": "Oslo" } }, "status": "ok", "time": 0.0 }),
+    );
+
+    let point: Point = client(&server).get_point("test", 7).await.unwrap();
+    assert_eq!(point.id, PointId::Num(7));
+    assert_eq!(serde_json::to_value(&point).unwrap()["id"], json!(7));
+    assert_eq!(server.requests()[0].path, "/collections/test/points/7");
+}
+
+#[tokio::test]
+async fn invalid_uuid_ids_are_rejected_before_sending() {
+    let server = MockServer::start().await;
+    let client = client(&server);
+    let id = PointId::Uuid("not-a-uuid".to_string());
+
+    let points: Vec<Point> = vec![Point {
+        id: id.clone(),
+        vector: Some(vec![0.1, 0.2].into()),
+        payload: None,
+    }];
+    let err = client.upsert_points("test", points).await.unwrap_err();
+    assert!(matches!(err, QdrantError::InvalidInput(_)), "{}", err);
+
+    let err = client.delete_points("test", [id]).await.unwrap_err();
+    assert!(matches!(err, QdrantError::InvalidInput(_)), "{}", err);
+
+    assert!(server.requests().is_empty());
+}
+
+/// The body of an acknowledged write
+fn completed() -> serde_json::Value {
+    json!({ "result": { "operation_id": 1, "status": "completed" }, "status": "ok", "time": 0.0 })
+}
+
+/// The body of a list of scored points
+fn scored(ids: &[u64]) -> serde_json::Value {
+    let hits: Vec<_> = ids
+        .iter()
+        .map(|id| json!({ "id": id, "version": 0, "score": 0.5 }))
+        .collect();
+    json!({ "result": hits, "status": "ok", "time": 0.0 })
+}
+
+#[tokio::test]
+async fn batch_update_sends_the_operations_in_order() {
+    let server = MockServer::start().await;
+    server.respond(200, &[], completed());
+
+    let operations: Vec<PointOperation> = vec![
+        PointOperation::Upsert {
+            points: vec![Point::builder()
+                .id(1)
+                .vector(vec![0.1, 0.2])
+                .build()
+                .unwrap()],
+        },
+        PointOperation::Delete {
+            points: vec![PointId::Num(2)],
+        },
+        PointOperation::DeleteByFilter {
+            filter: json!({ "must": [{ "key": "stale", "match": { "value": true } }] }),
+        },
+        PointOperation::SetPayload {
+            payload: json!({ "city": "Oslo" }).as_object().cloned().unwrap(),
+            points: vec![PointId::Num(1)],
+        },
+        PointOperation::DeleteVectors {
+            points: vec![PointId::Num(1)],
+            vector: vec!["image".to_string()],
+        },
+    ];
+    client(&server)
+        .batch_update("test", operations)
+        .await
+        .unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.path, "/collections/test/points/batch?wait=true");
+    assert_eq!(
+        request.json(),
+        json!({
+            "operations": [
+                { "upsert": { "points": [{ "id": 1, "vector": [0.1f32, 0.2f32] }] } },
+                { "delete": { "points": [2] } },
+                { "delete": { "filter": { "must": [{ "key": "stale", "match": { "value": true } }] } } },
+                { "set_payload": { "payload": { "city": "Oslo" }, "points": [1] } },
+                { "delete_vectors": { "points": [1], "vector": ["image"] } },
+            ]
+        })
+    );
+}
+
+#[tokio::test]
+async fn query_points_and_hybrid_search_send_prefetches_and_fusion() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "points": [{ "id": 1, "version": 0, "score": 0.9 }] }, "status": "ok", "time": 0.0 }),
+    );
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "points": [{ "id": 2, "version": 0, "score": 0.8 }] }, "status": "ok", "time": 0.0 }),
+    );
+
+    let client = client(&server);
+    let request = QueryRequest {
+        query: Some(Query::Nearest(VectorInput::Dense(vec![0.1, 0.2]))),
+        using: Some("text".to_string()),
+        limit: Some(5),
+        ..Default::default()
+    };
+    let hits: Vec<ScoredPoint> = client.query_points("test", request).await.unwrap();
+    assert_eq!(hits[0].id, PointId::Num(1));
+
+    let prefetches = vec![
+        Prefetch {
+            query: Some(Query::Nearest(VectorInput::Dense(vec![0.1, 0.2]))),
+            using: Some("dense".to_string()),
+            limit: Some(20),
+            ..Default::default()
+        },
+        Prefetch {
+            query: Some(Query::Nearest(VectorInput::Id(PointId::Num(7)))),
+            using: Some("image".to_string()),
+            limit: Some(20),
+            ..Default::default()
+        },
+    ];
+    let hits: Vec<ScoredPoint> = client
+        .hybrid_search("test", prefetches, Fusion::Rrf, 10)
+        .await
+        .unwrap();
+    assert_eq!(hits[0].id, PointId::Num(2));
+
+    let requests = server.requests();
+    assert!(requests
+        .iter()
+        .all(|r| r.method == "POST" && r.path == "/collections/test/points/query"));
+    assert_eq!(
+        requests[0].json(),
+        json!({
+            "query": { "nearest": [0.1f32, 0.2f32] },
+            "using": "text",
+            "limit": 5,
+            "with_payload": true,
+            "with_vector": true,
+        })
+    );
+    assert_eq!(
+        requests[1].json(),
+        json!({
+            "prefetch": [
+                { "query": { "nearest": [0.1f32, 0.2f32] }, "using": "dense", "limit": 20 },
+                { "query": { "nearest": 7 }, "using": "image", "limit": 20 },
+            ],
+            "query": { "fusion": "rrf" },
+            "limit": 10,
+            "with_payload": true,
+            "with_vector": true,
+        })
+    );
+}
+
+#[tokio::test]
+async fn recommendations_and_discovery_send_their_examples() {
+    let server = MockServer::start().await;
+    server.respond(200, &[], scored(&[3]));
+    server.respond(
+        200,
+        &[],
+        json!({ "result": [[{ "id": 4, "version": 0, "score": 0.4 }], []], "status": "ok", "time": 0.0 }),
+    );
+    server.respond(
+        200,
+        &[],
+        json!({
+            "result": { "groups": [{ "id": "a", "hits": [{ "id": 5, "version": 0, "score": 0.3 }] }] },
+            "status": "ok",
+            "time": 0.0,
+        }),
+    );
+    server.respond(200, &[], scored(&[6]));
+    server.respond(200, &[], scored(&[7]));
+
+    let client = client(&server);
+    let request = RecommendRequest {
+        positive: vec![VectorInput::Id(PointId::Num(1))],
+        negative: vec![VectorInput::Id(PointId::Num(2))],
+        strategy: Some(RecommendStrategy::BestScore),
+        limit: 3,
+        ..Default::default()
+    };
+
+    let hits: Vec<ScoredPoint> = client
+        .recommend_points("test", request.clone())
+        .await
+        .unwrap();
+    assert_eq!(hits[0].id, PointId::Num(3));
+
+    let batches: Vec<Vec<ScoredPoint>> = client
+        .recommend_batch("test", vec![request.clone(), request.clone()])
+        .await
+        .unwrap();
+    assert_eq!(batches.len(), 2);
+    assert_eq!(batches[0][0].id, PointId::Num(4));
+    assert!(batches[1].is_empty());
+
+    let groups: Vec<PointGroup> = client
+        .recommend_groups("test", request, "doc_id", 2)
+        .await
+        .unwrap();
+    assert_eq!(groups[0].id, "a");
+    assert_eq!(groups[0].hits[0].id, PointId::Num(5));
+
+    let hits: Vec<ScoredPoint> = client
+        .recommend_by_vectors("test", vec![vec![0.1, 0.2]], vec![vec![0.3, 0.4]], 4, None)
+        .await
+        .unwrap();
+    assert_eq!(hits[0].id, PointId::Num(6));
+
+    let hits: Vec<ScoredPoint> = client
+        .discover_points(
+            "test",
+            VectorInput::Id(PointId::Num(1)),
+            vec![(PointId::Num(2), PointId::Num(3))],
+            5,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(hits[0].id, PointId::Num(7));
+
+    let requests = server.requests();
+    let paths: Vec<&str> = requests.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/collections/test/points/recommend",
+            "/collections/test/points/recommend/batch",
+            "/collections/test/points/recommend/groups",
+            "/collections/test/points/recommend",
+            "/collections/test/points/discover",
+        ]
+    );
+    assert!(requests.iter().all(|r| r.method == "POST"));
+
+    let recommend = json!({
+        "positive": [1],
+        "negative": [2],
+        "strategy": "best_score",
+        "limit": 3,
+        "with_payload": true,
+        "with_vector": true,
+    });
+    assert_eq!(requests[0].json(), recommend);
+    assert_eq!(
+        requests[1].json(),
+        json!({ "searches": [recommend, recommend] })
+    );
+    let grouped = requests[2].json();
+    assert_eq!(grouped["positive"], json!([1]));
+    assert_eq!(grouped["group_by"], "doc_id");
+    assert_eq!(grouped["group_size"], 2);
+    assert_eq!(
+        requests[3].json(),
+        json!({
+            "positive": [[0.1f32, 0.2f32]],
+            "negative": [[0.3f32, 0.4f32]],
+            "limit": 4,
+            "with_payload": true,
+            "with_vector": true,
+        })
+    );
+    assert_eq!(
+        requests[4].json(),
+        json!({
+            "target": 1,
+            "context": [{ "positive": 2, "negative": 3 }],
+            "limit": 5,
+            "with_payload": true,
+            "with_vector": true,
+        })
+    );
+}
+
+#[tokio::test]
+async fn facet_and_search_matrix_parse_their_results() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({
+            "result": { "hits": [{ "value": "Berlin", "count": 3 }, { "value": "Oslo", "count": 1 }] },
+            "status": "ok",
+            "time": 0.0,
+        }),
+    );
+    server.respond(
+        200,
+        &[],
+        json!({
+            "result": { "pairs": [{ "a": 1, "b": 2, "score": 0.9 }] },
+            "status": "ok",
+            "time": 0.0,
+        }),
+    );
+
+    let client = client(&server);
+    let filter = json!({ "must": [{ "key": "country", "match": { "value": "DE" } }] });
+    let counts = client
+        .facet("test", "city", Some(filter.clone()), 10)
+        .await
+        .unwrap();
+    assert_eq!(counts, vec![(json!("Berlin"), 3), (json!("Oslo"), 1)]);
+
+    let pairs = client.search_matrix("test", 100, 2, None).await.unwrap();
+    assert_eq!(pairs[0].a, PointId::Num(1));
+    assert_eq!(pairs[0].b, PointId::Num(2));
+    assert_eq!(pairs[0].score, 0.9);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/collections/test/facet");
+    assert_eq!(
+        requests[0].json(),
+        json!({ "key": "city", "limit": 10, "filter": filter })
+    );
+    assert_eq!(requests[1].method, "POST");
+    assert_eq!(
+        requests[1].path,
+        "/collections/test/points/search/matrix/pairs"
+    );
+    assert_eq!(requests[1].json(), json!({ "sample": 100, "limit": 2 }));
+}
+
+#[tokio::test]
+async fn payload_indexes_and_collection_updates_use_their_methods() {
+    let server = MockServer::start().await;
+    server.respond(200, &[], completed());
+    server.respond(200, &[], completed());
+    server.respond(
+        200,
+        &[],
+        json!({ "result": true, "status": "ok", "time": 0.0 }),
+    );
+
+    let client = client(&server);
+    client
+        .create_payload_index("test", "city", PayloadSchemaType::Keyword)
+        .await
+        .unwrap();
+    client.delete_payload_index("test", "city").await.unwrap();
+    let options = UpdateCollectionOptions {
+        optimizers_config: Some(OptimizersConfigDiff {
+            indexing_threshold: Some(20000),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    client.update_collection("test", options).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "PUT");
+    assert_eq!(requests[0].path, "/collections/test/index?wait=true");
+    assert_eq!(
+        requests[0].json(),
+        json!({ "field_name": "city", "field_schema": "keyword" })
+    );
+    assert_eq!(requests[1].method, "DELETE");
+    assert_eq!(requests[1].path, "/collections/test/index/city?wait=true");
+    assert_eq!(requests[2].method, "PATCH");
+    assert_eq!(requests[2].path, "/collections/test");
+    assert_eq!(
+        requests[2].json(),
+        json!({ "optimizers_config": { "indexing_threshold": 20000 } })
+    );
+}
+
+#[tokio::test]
+async fn set_payload_sends_the_key_and_overwrite_payload_uses_put() {
+    let server = MockServer::start().await;
+    server.respond(200, &[], completed());
+    server.respond(200, &[], completed());
+
+    let client = client(&server);
+    let payload = json!({ "rating": 5 }).as_object().cloned().unwrap();
+    client
+        .set_payload("test", payload.clone(), [1, 2], Some("review"))
+        .await
+        .unwrap();
+    client
+        .overwrite_payload("test", payload, [3])
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(
+        requests[0].path,
+        "/collections/test/points/payload?wait=true"
+    );
+    assert_eq!(
+        requests[0].json(),
+        json!({ "payload": { "rating": 5 }, "points": [1, 2], "key": "review" })
+    );
+    assert_eq!(requests[1].method, "PUT");
+    assert_eq!(
+        requests[1].path,
+        "/collections/test/points/payload?wait=true"
+    );
+    assert_eq!(
+        requests[1].json(),
+        json!({ "payload": { "rating": 5 }, "points": [3] })
+    );
+}
+
+#[tokio::test]
+async fn excluded_payload_keys_are_sent_as_an_exclude_object() {
+    let server = MockServer::start().await;
+    server.respond(200, &[], scored(&[]));
+
+    let options = SearchOptions {
+        with_payload: PayloadSelector::Exclude(vec!["embedding_text".to_string()]),
+        with_vector: false,
+        ..Default::default()
+    };
+    let _: Vec<ScoredPoint> = client(&server)
+        .search_points_with_options("test", vec![0.1, 0.2], 3, &options)
+        .await
+        .unwrap();
+
+    let body = server.requests()[0].json();
+    assert_eq!(
+        body["with_payload"],
+        json!({ "exclude": ["embedding_text"] })
+    );
+    assert_eq!(body["with_vector"], false);
+}
+
+#[tokio::test]
+async fn gzip_compresses_request_bodies() {
+    use std::io::Read;
+
+    let server = MockServer::start().await;
+    server.respond(200, &[], completed());
+
+    let client = QdrantBuilder::new()
+        .url(server.url())
+        .gzip(true)
+        .build()
+        .unwrap();
+    let points = vec![Point::builder()
+        .id(1)
+        .vector(vec![0.1, 0.2])
+        .build()
+        .unwrap()];
+    client.upsert_points("test", points).await.unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.header("content-encoding"), Some("gzip"));
+    assert_eq!(request.header("content-type"), Some("application/json"));
+    let mut body = String::new();
+    flate2::read::GzDecoder::new(request.body.as_slice())
+        .read_to_string(&mut body)
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["points"][0]["id"], 1);
+}
+
+#[tokio::test]
+async fn shard_keys_are_created_and_deleted() {
+    let server = MockServer::start().await;
+    server.respond(
+        200,
+        &[],
+        json!({ "result": true, "status": "ok", "time": 0.0 }),
+    );
+    server.respond(
+        200,
+        &[],
+        json!({ "result": true, "status": "ok", "time": 0.0 }),
+    );
+
+    let client = client(&server);
+    client
+        .create_shard_key("test", ShardKey::from("tenant-a"))
+        .await
+        .unwrap();
+    client
+        .delete_shard_key("test", ShardKey::Number(7))
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "PUT");
+    assert_eq!(requests[0].path, "/collections/test/shards");
+    assert_eq!(requests[0].json(), json!({ "shard_key": "tenant-a" }));
+    assert_eq!(requests[1].method, "POST");
+    assert_eq!(requests[1].path, "/collections/test/shards/delete");
+    assert_eq!(requests[1].json(), json!({ "shard_key": 7 }));
+}
+
+#[tokio::test]
+async fn server_info_endpoints_are_read() {
+    let server = MockServer::start().await;
+    server.respond(200, &[], json!("app_info{name=\"qdrant\"} 1"));
+    server.respond(
+        200,
+        &[],
+        json!({ "result": { "id": "node", "app": { "version": "1.12.0" } }, "status": "ok", "time": 0.0 }),
+    );
+    server.respond(
+        200,
+        &[],
+        json!({
+            "result": {
+                "status": "enabled",
+                "peer_id": 1,
+                "peers": { "1": { "uri": "http://qdrant-0:6335/" } },
+                "raft_info": { "term": 3 },
+            },
+            "status": "ok",
+            "time": 0.0,
+        }),
+    );
+
+    let client = client(&server);
+    assert!(client.metrics().await.unwrap().contains("app_info"));
+    assert_eq!(
+        client.telemetry().await.unwrap()["app"]["version"],
+        "1.12.0"
+    );
+    let cluster = client.cluster_info().await.unwrap();
+    assert_eq!(cluster.status, "enabled");
+    assert_eq!(cluster.peer_id, Some(1));
+    assert_eq!(cluster.peers[&1].uri, "http://qdrant-0:6335/");
+
+    let requests = server.requests();
+    let calls: Vec<(&str, &str)> = requests
+        .iter()
+        .map(|r| (r.method.as_str(), r.path.as_str()))
+        .collect();
+    assert_eq!(
+        calls,
+        vec![
+            ("GET", "/metrics"),
+            ("GET", "/telemetry"),
+            ("GET", "/cluster")
+        ]
+    );
+    assert!(requests
+        .iter()
+        .all(|r| r.header("api-key") == Some("secret")));
+}
@@ -0,0 +1,156 @@
+//! A minimal HTTP server standing in for Qdrant in the tests.
+//!
+//! It answers the requests with the queued responses, in order, and records the requests
+//! so the tests can check what the client sent. It only relies on tokio's TCP support,
+//! so the tests also run on WasmEdge.
+
+#![allow(dead_code)]
+
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A request received by the mock server
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// The path with the query string, e.g. `/collections/test/points?wait=true`
+    pub path: String,
+    /// The headers, with lowercase names
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The body parsed as JSON
+    pub fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).expect("the request body is not JSON")
+    }
+}
+
+#[derive(Default)]
+struct State {
+    /// The status, the extra headers and the body of the queued responses
+    responses: VecDeque<(u16, String, String)>,
+    requests: Vec<RecordedRequest>,
+}
+
+pub struct MockServer {
+    url: String,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockServer {
+    pub async fn start() -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let server_state = state.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, server_state.clone()));
+            }
+        });
+
+        MockServer { url, state }
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// Queues the response to the next unanswered request, with the given headers
+    /// besides `content-type` and `content-length`, e.g. `&[("retry-after", "0")]`
+    pub fn respond(&self, status: u16, headers: &[(&str, &str)], body: Value) {
+        let headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}: {}\r\n", name, value))
+            .collect();
+        self.state
+            .lock()
+            .unwrap()
+            .responses
+            .push_back((status, headers, body.to_string()));
+    }
+
+    /// The requests received so far, in order
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+/// Answers the requests of a connection until the client closes it
+async fn serve(stream: TcpStream, state: Arc<Mutex<State>>) {
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut request_line = String::new();
+        match reader.read_line(&mut request_line).await {
+            Ok(0) | Err(_) => return,
+            Ok(_) => (),
+        }
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let path = parts.next().unwrap_or_default().to_string();
+
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).await.is_err() {
+                return;
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+            }
+        }
+
+        let length = headers
+            .iter()
+            .find(|(name, _)| name == "content-length")
+            .and_then(|(_, value)| value.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; length];
+        if reader.read_exact(&mut body).await.is_err() {
+            return;
+        }
+
+        let (status, extra_headers, response) = {
+            let mut state = state.lock().unwrap();
+            state.requests.push(RecordedRequest {
+                method,
+                path,
+                headers,
+                body,
+            });
+            state.responses.pop_front().unwrap_or((
+                500,
+                String::new(),
+                r#"{"status":{"error":"no response queued"}}"#.to_string(),
+            ))
+        };
+
+        let reply = format!(
+            "HTTP/1.1 {} Mock\r\ncontent-type: application/json\r\ncontent-length: {}\r\n{}\r\n{}",
+            status,
+            response.len(),
+            extra_headers,
+            response
+        );
+        if reader.get_mut().write_all(reply.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
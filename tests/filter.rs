@@ -0,0 +1,227 @@
+use qdrant::filter::*;
+use qdrant::{PointId, SearchOptions};
+use serde_json::{json, Value};
+
+#[test]
+fn match_text_differs_from_exact_match() {
+    let filter: Value = Filter::default()
+        .must(Condition::match_text("body", "vector database"))
+        .must(Condition::matches("city", "Berlin"))
+        .into();
+
+    assert_eq!(
+        filter,
+        json!({
+            "must": [
+                { "key": "body", "match": { "text": "vector database" } },
+                { "key": "city", "match": { "value": "Berlin" } },
+            ]
+        })
+    );
+}
+
+#[test]
+fn range_leaves_unset_bounds_out() {
+    let filter: Value = Filter::default()
+        .must_not(Condition::range(
+            "price",
+            Range {
+                gte: Some(10.0),
+                lt: Some(20.0),
+                ..Default::default()
+            },
+        ))
+        .into();
+
+    assert_eq!(
+        filter,
+        json!({ "must_not": [{ "key": "price", "range": { "gte": 10.0, "lt": 20.0 } }] })
+    );
+}
+
+#[test]
+fn geo_conditions() {
+    let berlin = GeoPoint {
+        lon: 13.4,
+        lat: 52.5,
+    };
+    let filter: Value = Filter::default()
+        .should(Condition::geo_radius("location", berlin, 1000.0))
+        .should(Condition::geo_bounding_box(
+            "location",
+            GeoPoint {
+                lon: 13.0,
+                lat: 53.0,
+            },
+            GeoPoint {
+                lon: 14.0,
+                lat: 52.0,
+            },
+        ))
+        .should(Condition::geo_polygon(
+            "location",
+            GeoPolygon {
+                exterior: GeoLineString {
+                    points: vec![
+                        GeoPoint {
+                            lon: 13.0,
+                            lat: 52.0,
+                        },
+                        GeoPoint {
+                            lon: 14.0,
+                            lat: 52.0,
+                        },
+                        GeoPoint {
+                            lon: 14.0,
+                            lat: 53.0,
+                        },
+                        GeoPoint {
+                            lon: 13.0,
+                            lat: 52.0,
+                        },
+                    ],
+                },
+                interiors: vec![],
+            },
+        ))
+        .into();
+
+    assert_eq!(
+        filter,
+        json!({
+            "should": [
+                {
+                    "key": "location",
+                    "geo_radius": { "center": { "lon": 13.4, "lat": 52.5 }, "radius": 1000.0 },
+                },
+                {
+                    "key": "location",
+                    "geo_bounding_box": {
+                        "top_left": { "lon": 13.0, "lat": 53.0 },
+                        "bottom_right": { "lon": 14.0, "lat": 52.0 },
+                    },
+                },
+                {
+                    "key": "location",
+                    "geo_polygon": {
+                        "exterior": {
+                            "points": [
+                                { "lon": 13.0, "lat": 52.0 },
+                                { "lon": 14.0, "lat": 52.0 },
+                                { "lon": 14.0, "lat": 53.0 },
+                                { "lon": 13.0, "lat": 52.0 },
+                            ]
+                        },
+                    },
+                },
+            ]
+        })
+    );
+}
+
+#[test]
+fn values_count_and_emptiness_conditions() {
+    let filter: Value = Filter::default()
+        .must(Condition::values_count(
+            "tags",
+            ValuesCount {
+                gt: Some(3),
+                ..Default::default()
+            },
+        ))
+        .must(Condition::is_null("deleted_at"))
+        .must_not(Condition::is_empty("title"))
+        .into();
+
+    assert_eq!(
+        filter,
+        json!({
+            "must": [
+                { "key": "tags", "values_count": { "gt": 3 } },
+                { "is_null": { "key": "deleted_at" } },
+            ],
+            "must_not": [{ "is_empty": { "key": "title" } }],
+        })
+    );
+}
+
+#[test]
+fn nested_condition() {
+    let filter: Value = Filter::default()
+        .must(Condition::nested(
+            "reviews",
+            Filter::default()
+                .must(Condition::range(
+                    "rating",
+                    Range {
+                        gte: Some(4.0),
+                        ..Default::default()
+                    },
+                ))
+                .must(Condition::matches("verified", true)),
+        ))
+        .into();
+
+    assert_eq!(
+        filter,
+        json!({
+            "must": [{
+                "nested": {
+                    "key": "reviews",
+                    "filter": {
+                        "must": [
+                            { "key": "rating", "range": { "gte": 4.0 } },
+                            { "key": "verified", "match": { "value": true } },
+                        ]
+                    }
+                }
+            }]
+        })
+    );
+}
+
+#[test]
+fn has_id_condition() {
+    let ids = [
+        PointId::Num(1),
+        PointId::Uuid("6f9d3b1c-3b7e-4c1a-9d2e-8f0a1b2c3d4e".to_string()),
+    ];
+    let filter: Value = Filter::default()
+        .must(Condition::has_id(&ids))
+        .must(Condition::matches("city", "Berlin"))
+        .into();
+
+    assert_eq!(
+        filter,
+        json!({
+            "must": [
+                { "has_id": [1, "6f9d3b1c-3b7e-4c1a-9d2e-8f0a1b2c3d4e"] },
+                { "key": "city", "match": { "value": "Berlin" } },
+            ]
+        })
+    );
+}
+
+#[test]
+fn search_request_body_includes_the_filter() {
+    let options = SearchOptions {
+        filter: Some(
+            Filter::default()
+                .must(Condition::matches("city", "Berlin"))
+                .into(),
+        ),
+        with_vector: false,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        options.request_body(vec![1.0, 0.0], 5),
+        json!({
+            "vector": [1.0, 0.0],
+            "limit": 5,
+            "with_payload": true,
+            "with_vector": false,
+            "filter": { "must": [{ "key": "city", "match": { "value": "Berlin" } }] },
+        })
+    );
+}